@@ -0,0 +1,559 @@
+//! Types and systems for playing animated tiles
+
+use bevy::prelude::{
+	Component,
+	Entity,
+	Event,
+	EventWriter,
+	Query,
+	Res,
+	Resource,
+	SystemSet,
+	TextureAtlasSprite,
+	Time,
+};
+use bevy_tileset_tiles::prelude::{AnimatedTileData, AnimationMode, TileType};
+
+use crate::prelude::Tileset;
+
+/// The system set containing the system that advances [`TileAnimation`] components over time
+///
+/// Systems that step animations manually should be ordered before this set so that the
+/// resulting frame is synced to the entity's `TextureAtlasSprite` in the same update.
+#[derive(SystemSet, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TileAnimationSystem;
+
+/// A resource scaling the speed of every timed [`TileAnimation`]
+///
+/// The default of `1.0` plays animations at their normal speed, while `0.0` freezes them on
+/// their current frame. This is useful for effects like slow motion.
+#[derive(Resource, Debug, Copy, Clone, PartialEq)]
+pub struct TileAnimationSpeed(pub f32);
+
+impl Default for TileAnimationSpeed {
+	fn default() -> Self { Self(1.0) }
+}
+
+/// A resource controlling whether timed [`TileAnimation`] components are advanced
+///
+/// Pausing holds every animation on its current frame, and resuming continues from there.
+/// Animations can still be stepped manually while paused.
+#[derive(Resource, Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct TileAnimationState {
+	/// Whether animations are paused
+	pub paused: bool,
+}
+
+impl TileAnimationState {
+	/// Pauses all timed animations
+	pub fn pause(&mut self) { self.paused = true; }
+
+	/// Resumes all timed animations
+	pub fn resume(&mut self) { self.paused = false; }
+}
+
+/// An event sent when a timed `Once` [`TileAnimation`] reaches its last frame
+///
+/// This is useful for cleaning up one-shot effects (such as explosions) once they finish.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct TileAnimationFinished {
+	/// The entity containing the finished animation
+	pub entity: Entity,
+	/// The name of the animated tile (empty if the animation wasn't created for a named tile)
+	pub tile: String,
+}
+
+/// A component containing the playback state of an animated tile
+///
+/// When added alongside a `TextureAtlasSprite`, the sprite's index is kept in sync with the
+/// current frame of the animation.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct TileAnimation {
+	/// The atlas index of the first frame
+	start: usize,
+	/// The atlas index of the last frame (inclusive)
+	end: usize,
+	/// The speed of the animation
+	speed: f32,
+	/// The explicit duration of each frame (in seconds)
+	durations: Vec<Option<f32>>,
+	/// The name of the animated tile
+	tile: Option<String>,
+	/// How the animation plays once it reaches its last frame
+	mode: AnimationMode,
+	/// The current frame (relative to the start of the animation)
+	frame: usize,
+	/// Whether a `PingPong` animation is playing backward
+	reversed: bool,
+	/// Whether a `Once` animation has reached its last frame
+	finished: bool,
+	/// The time spent on the current frame (in seconds)
+	elapsed: f32,
+	/// Whether the animation is advanced automatically over time
+	///
+	/// Disable this to step the animation manually with [`TileAnimation::advance`] or
+	/// [`TileAnimation::set_frame`].
+	pub timed: bool,
+}
+
+impl TileAnimation {
+	/// Creates a new animation over the given (inclusive) range of atlas indices
+	///
+	/// Each frame lasts `1 / speed` seconds.
+	///
+	/// # Arguments
+	///
+	/// * `start`: The atlas index of the first frame
+	/// * `end`: The atlas index of the last frame (inclusive)
+	/// * `speed`: The speed of the animation
+	///
+	/// returns: TileAnimation
+	///
+	pub fn new(start: usize, end: usize, speed: f32) -> Self {
+		Self {
+			start,
+			end: end.max(start),
+			speed,
+			durations: Vec::new(),
+			tile: None,
+			mode: AnimationMode::Loop,
+			frame: 0,
+			reversed: false,
+			finished: false,
+			elapsed: 0.0,
+			timed: true,
+		}
+	}
+
+	/// Sets how the animation plays once it reaches its last frame
+	///
+	/// # Arguments
+	///
+	/// * `mode`: The playback mode
+	///
+	/// returns: TileAnimation
+	///
+	pub fn with_mode(mut self, mode: AnimationMode) -> Self {
+		self.mode = mode;
+		self.set_frame(self.frame);
+		self
+	}
+
+	/// Sets the name of the tile this animation plays
+	///
+	/// # Arguments
+	///
+	/// * `tile`: The name of the tile
+	///
+	/// returns: TileAnimation
+	///
+	pub fn with_tile(mut self, tile: impl Into<String>) -> Self {
+		self.tile = Some(tile.into());
+		self
+	}
+
+	/// Creates a new animation for the animated tile with the given name
+	///
+	/// Any explicit frame durations and the playback mode defined for the tile are used.
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset containing the tile
+	/// * `name`: The name of the tile
+	///
+	/// returns: Option<TileAnimation>
+	///
+	pub fn from_tileset(tileset: &Tileset, name: &str) -> Option<Self> {
+		match tileset.get_tile_data(name)?.tile() {
+			TileType::Animated(anim) => Some(Self::from_animated(tileset, anim).with_tile(name)),
+			_ => None,
+		}
+	}
+
+	/// Creates a new animation from the data of an animated tile
+	///
+	/// This is useful for animations nested in other tiles, such as the animated variants of a
+	/// Variant tile.
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset containing the animation
+	/// * `anim`: The animated tile data
+	///
+	/// returns: TileAnimation
+	///
+	pub fn from_animated(tileset: &Tileset, anim: &AnimatedTileData) -> Self {
+		let mut animation =
+			Self::new(anim.start(), anim.end(), anim.speed()).with_mode(anim.mode());
+		animation.durations = (anim.start()..=anim.end())
+			.map(|index| tileset.get_frame_duration(&index))
+			.collect();
+		animation
+	}
+
+	/// Starts the animation at a pseudo-random point derived from the given seed
+	///
+	/// The same seed always results in the same starting point. `Once` animations are left
+	/// unchanged.
+	///
+	/// # Arguments
+	///
+	/// * `seed`: The seed used to choose the starting point
+	///
+	/// returns: TileAnimation
+	///
+	pub fn with_phase_seed(mut self, seed: u64) -> Self {
+		let frames = 0..self.frame_count();
+		let cycle: f32 = match self.mode {
+			AnimationMode::Loop => frames.map(|frame| self.frame_duration(frame)).sum(),
+			AnimationMode::Once => return self,
+			AnimationMode::PingPong => frames
+				.chain((1..self.frame_count().saturating_sub(1)).rev())
+				.map(|frame| self.frame_duration(frame))
+				.sum(),
+		};
+		if cycle.is_finite() && cycle > 0.0 {
+			let fraction = (splitmix64(seed) >> 40) as f32 / (1u64 << 24) as f32;
+			self.tick(cycle * fraction);
+		}
+		self
+	}
+
+	/// Gets the atlas index of the current frame
+	///
+	/// This is the value to use for a `TextureAtlasSprite::index`.
+	pub fn index(&self) -> usize { self.start + self.frame }
+
+	/// Gets the current frame (relative to the start of the animation)
+	pub fn frame(&self) -> usize { self.frame }
+
+	/// Gets the number of frames in this animation
+	pub fn frame_count(&self) -> usize { self.end - self.start + 1 }
+
+	/// Gets the speed of this animation
+	pub fn speed(&self) -> f32 { self.speed }
+
+	/// Gets the name of the tile this animation plays, if known
+	pub fn tile(&self) -> Option<&str> { self.tile.as_deref() }
+
+	/// Gets how this animation plays once it reaches its last frame
+	pub fn mode(&self) -> AnimationMode { self.mode }
+
+	/// Checks if this is a `Once` animation that has reached its last frame
+	///
+	/// Finished animations are no longer advanced over time. Use [`TileAnimation::set_frame`]
+	/// to play them again.
+	pub fn is_finished(&self) -> bool { self.finished }
+
+	/// Gets the duration of the given frame (in seconds)
+	pub fn frame_duration(&self, frame: usize) -> f32 {
+		self.durations
+			.get(frame)
+			.copied()
+			.flatten()
+			.unwrap_or(1.0 / self.speed)
+	}
+
+	/// Advances the animation by the given number of frames
+	///
+	/// What happens past the last frame depends on the [`AnimationMode`]: `Loop` wraps around
+	/// to the start, `Once` stops on the last frame, and `PingPong` reverses direction.
+	///
+	/// # Arguments
+	///
+	/// * `frames`: The number of frames to advance
+	///
+	/// returns: ()
+	///
+	pub fn advance(&mut self, frames: usize) {
+		match self.mode {
+			AnimationMode::Loop => self.set_frame(self.frame + frames % self.frame_count()),
+			AnimationMode::Once => self.set_frame(
+				self.frame
+					.saturating_add(frames)
+					.min(self.frame_count() - 1),
+			),
+			AnimationMode::PingPong => {
+				let last = self.frame_count() - 1;
+				if last == 0 {
+					return self.set_frame(0);
+				}
+				// Position within a full forward and backward cycle
+				let position = if self.reversed {
+					2 * last - self.frame
+				} else {
+					self.frame
+				};
+				let position = (position + frames % (2 * last)) % (2 * last);
+				self.elapsed = 0.0;
+				self.reversed = position >= last;
+				self.frame = if self.reversed {
+					2 * last - position
+				} else {
+					position
+				};
+			},
+		}
+	}
+
+	/// Sets the current frame (relative to the start of the animation)
+	///
+	/// Frames past the end of the animation wrap around to the start. This also resets
+	/// the time spent on the current frame, marks `Once` animations as finished if this is the
+	/// last frame, and makes `PingPong` animations play forward.
+	///
+	/// # Arguments
+	///
+	/// * `frame`: The frame to display
+	///
+	/// returns: ()
+	///
+	pub fn set_frame(&mut self, frame: usize) {
+		self.frame = frame % self.frame_count();
+		self.elapsed = 0.0;
+		self.reversed = false;
+		self.finished = self.mode == AnimationMode::Once && self.frame == self.frame_count() - 1;
+	}
+
+	/// Advances the animation by the given amount of time (in seconds)
+	///
+	/// # Arguments
+	///
+	/// * `delta`: The elapsed time (in seconds)
+	///
+	/// returns: ()
+	///
+	pub fn tick(&mut self, delta: f32) {
+		let mut elapsed = self.elapsed + delta;
+		loop {
+			if self.finished {
+				return;
+			}
+			let duration = self.frame_duration(self.frame);
+			if !duration.is_finite() || duration <= 0.0 || elapsed < duration {
+				break;
+			}
+			elapsed -= duration;
+			self.advance(1);
+		}
+		self.elapsed = elapsed;
+	}
+}
+
+/// Scrambles the bits of a seed (see <https://prng.di.unimi.it/splitmix64.c>)
+fn splitmix64(seed: u64) -> u64 {
+	let mut value = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+	value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+	value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+	value ^ (value >> 31)
+}
+
+/// System that advances timed [`TileAnimation`] components and syncs their sprites
+///
+/// Sends a [`TileAnimationFinished`] event for each `Once` animation that finishes.
+pub(crate) fn animate_tiles(
+	time: Res<Time>,
+	speed: Res<TileAnimationSpeed>,
+	state: Res<TileAnimationState>,
+	mut query: Query<(Entity, &mut TileAnimation, Option<&mut TextureAtlasSprite>)>,
+	mut finished: EventWriter<TileAnimationFinished>,
+) {
+	let delta = time.delta_seconds() * speed.0;
+	for (entity, mut animation, sprite) in query.iter_mut() {
+		if animation.timed && !state.paused && !animation.is_finished() {
+			animation.tick(delta);
+			if animation.is_finished() {
+				finished.send(TileAnimationFinished {
+					entity,
+					tile: animation.tile().unwrap_or_default().to_string(),
+				});
+			}
+		}
+		if let Some(mut sprite) = sprite {
+			if sprite.index != animation.index() {
+				sprite.index = animation.index();
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use bevy::prelude::{App, Events, Time, Update};
+	use bevy_tileset_tiles::prelude::AnimationMode;
+
+	use super::{
+		animate_tiles,
+		TileAnimation,
+		TileAnimationFinished,
+		TileAnimationSpeed,
+		TileAnimationState,
+	};
+
+	#[test]
+	fn should_step_manually() {
+		let mut animation = TileAnimation::new(4, 7, 1.0);
+		assert_eq!(4, animation.index());
+
+		animation.advance(2);
+		assert_eq!(6, animation.index());
+
+		animation.advance(3);
+		assert_eq!(1, animation.frame());
+		assert_eq!(5, animation.index());
+
+		animation.set_frame(3);
+		assert_eq!(7, animation.index());
+	}
+
+	#[test]
+	fn should_tick_with_frame_durations() {
+		let mut animation = TileAnimation::new(0, 2, 2.0);
+		animation.durations = vec![Some(1.0), None, None];
+
+		animation.tick(0.75);
+		assert_eq!(0, animation.frame());
+
+		animation.tick(0.5);
+		assert_eq!(1, animation.frame());
+
+		animation.tick(0.5);
+		assert_eq!(2, animation.frame());
+	}
+
+	#[test]
+	fn should_play_once() {
+		let mut animation = TileAnimation::new(0, 2, 1.0).with_mode(AnimationMode::Once);
+
+		animation.tick(1.5);
+		assert_eq!(1, animation.frame());
+		assert!(!animation.is_finished());
+
+		animation.tick(5.0);
+		assert_eq!(2, animation.frame());
+		assert!(animation.is_finished());
+
+		animation.set_frame(0);
+		assert!(!animation.is_finished());
+	}
+
+	#[test]
+	fn should_ping_pong() {
+		let mut animation = TileAnimation::new(0, 2, 1.0).with_mode(AnimationMode::PingPong);
+
+		let frames = (0..6)
+			.map(|_| {
+				animation.advance(1);
+				animation.frame()
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(vec![1, 2, 1, 0, 1, 2], frames);
+
+		animation.advance(3);
+		assert_eq!(1, animation.frame());
+	}
+
+	#[test]
+	fn should_send_finished_event() {
+		let mut app = App::new();
+		app.init_resource::<Time>()
+			.init_resource::<TileAnimationSpeed>()
+			.init_resource::<TileAnimationState>()
+			.add_event::<TileAnimationFinished>()
+			.add_systems(Update, animate_tiles);
+		let entity = app
+			.world
+			.spawn(
+				TileAnimation::new(0, 2, 1.0)
+					.with_mode(AnimationMode::Once)
+					.with_tile("Splash"),
+			)
+			.id();
+
+		app.world
+			.resource_mut::<Time>()
+			.advance_by(Duration::from_secs(5));
+		app.update();
+		// The animation has already finished, so it shouldn't be sent again
+		app.update();
+
+		let events = app.world.resource::<Events<TileAnimationFinished>>();
+		let sent = events
+			.get_reader()
+			.read(events)
+			.cloned()
+			.collect::<Vec<_>>();
+		assert_eq!(
+			vec![TileAnimationFinished {
+				entity,
+				tile: String::from("Splash"),
+			}],
+			sent
+		);
+	}
+
+	#[test]
+	fn should_scale_animation_speed() {
+		let mut app = App::new();
+		app.init_resource::<Time>()
+			.insert_resource(TileAnimationSpeed(0.5))
+			.init_resource::<TileAnimationState>()
+			.add_event::<TileAnimationFinished>()
+			.add_systems(Update, animate_tiles);
+		let entity = app.world.spawn(TileAnimation::new(0, 3, 1.0)).id();
+
+		app.world
+			.resource_mut::<Time>()
+			.advance_by(Duration::from_secs(2));
+		app.update();
+		assert_eq!(1, app.world.get::<TileAnimation>(entity).unwrap().frame());
+
+		app.insert_resource(TileAnimationSpeed(0.0));
+		app.update();
+		assert_eq!(1, app.world.get::<TileAnimation>(entity).unwrap().frame());
+	}
+
+	#[test]
+	fn should_pause_and_resume() {
+		let mut app = App::new();
+		app.init_resource::<Time>()
+			.init_resource::<TileAnimationSpeed>()
+			.init_resource::<TileAnimationState>()
+			.add_event::<TileAnimationFinished>()
+			.add_systems(Update, animate_tiles);
+		let entity = app.world.spawn(TileAnimation::new(0, 3, 1.0)).id();
+
+		app.world
+			.resource_mut::<Time>()
+			.advance_by(Duration::from_secs(1));
+		app.update();
+		assert_eq!(1, app.world.get::<TileAnimation>(entity).unwrap().frame());
+
+		app.world.resource_mut::<TileAnimationState>().pause();
+		app.update();
+		assert_eq!(1, app.world.get::<TileAnimation>(entity).unwrap().frame());
+
+		app.world.resource_mut::<TileAnimationState>().resume();
+		app.update();
+		assert_eq!(2, app.world.get::<TileAnimation>(entity).unwrap().frame());
+	}
+
+	#[test]
+	fn should_offset_phase_by_seed() {
+		let animation = TileAnimation::new(0, 7, 1.0);
+		assert_eq!(
+			animation.clone().with_phase_seed(3),
+			animation.clone().with_phase_seed(3)
+		);
+
+		let frames = (0..16)
+			.map(|seed| animation.clone().with_phase_seed(seed).frame())
+			.collect::<std::collections::HashSet<_>>();
+		assert!(frames.len() > 1);
+
+		let once = TileAnimation::new(0, 7, 1.0).with_mode(AnimationMode::Once);
+		assert_eq!(0, once.with_phase_seed(3).frame());
+	}
+}
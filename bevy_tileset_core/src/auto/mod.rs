@@ -4,11 +4,13 @@ use bevy::prelude::Component;
 
 pub use auto_tiler::AutoTiler;
 pub use traits::{AutoTile, AutoTileRequest, AutoTilemap};
+pub use update::{update_auto_tiles, AutoTileNeighbors, AutoTileSprite, AutoTileUpdatePlugin};
 
 use crate::ids::{TileGroupId, TileId, TilesetId};
 
 mod auto_tiler;
 mod traits;
+mod update;
 
 /// A component used to ID an Auto Tile
 ///
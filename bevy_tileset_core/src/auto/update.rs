@@ -0,0 +1,310 @@
+use std::{
+	collections::{HashMap, HashSet},
+	marker::PhantomData,
+};
+
+use bevy::{
+	app::{App, Plugin, Update},
+	ecs::system::{StaticSystemParam, SystemParam, SystemParamItem},
+	math::IVec2,
+	prelude::{
+		Added,
+		Changed,
+		Component,
+		Entity,
+		Local,
+		Or,
+		ParamSet,
+		Query,
+		RemovedComponents,
+		TextureAtlasSprite,
+	},
+};
+use bevy_tileset_tiles::prelude::{AutoTileRule, NeighborMask};
+
+use crate::{auto::AutoTileId, prelude::Tilesets};
+
+/// A component marking an auto tile entity whose `TextureAtlasSprite` should be kept in sync
+/// with its neighbors
+///
+/// The entity must also have an [`AutoTileId`]. Its sprite is updated by the system added with
+/// the [`AutoTileUpdatePlugin`] whenever it or one of its neighbors is added, changed, or
+/// removed. Neighbors only match if they have the same [`AutoTileId`].
+#[derive(Component, Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct AutoTileSprite {
+	/// The neighbors matching this tile as of its last update
+	pub neighbors: NeighborMask,
+}
+
+/// A trait for finding the neighbors of a tile entity
+///
+/// This allows [`AutoTileSprite`] entities to be updated regardless of how the tilemap stores
+/// its tiles. Implement this for a [`SystemParam`] that has access to that storage (such as a
+/// `Query` or `Res` of the tilemap).
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ecs::system::{SystemParam, SystemParamItem};
+/// # use bevy::math::IVec2;
+/// # use bevy::prelude::*;
+/// # use bevy::utils::HashMap;
+/// # use bevy_tileset_core::auto::AutoTileNeighbors;
+/// #[derive(Resource)]
+/// struct MyTilemap {
+/// 	positions: HashMap<Entity, IVec2>,
+/// 	tiles: HashMap<IVec2, Entity>,
+/// }
+///
+/// #[derive(SystemParam)]
+/// struct MyNeighbors<'w> {
+/// 	tilemap: Res<'w, MyTilemap>,
+/// }
+///
+/// impl AutoTileNeighbors for MyNeighbors<'static> {
+/// 	fn get_neighbor(
+/// 		param: &SystemParamItem<Self>,
+/// 		entity: Entity,
+/// 		offset: IVec2,
+/// 	) -> Option<Entity> {
+/// 		let pos = *param.tilemap.positions.get(&entity)?;
+/// 		param.tilemap.tiles.get(&(pos + offset)).copied()
+/// 	}
+/// }
+/// ```
+pub trait AutoTileNeighbors: SystemParam + 'static {
+	/// Get the entity neighboring the given tile entity
+	///
+	/// # Arguments
+	///
+	/// * `param`: The system parameter
+	/// * `entity`: The tile entity
+	/// * `offset`: The offset of the neighbor (with +Y pointing north)
+	///
+	/// returns: Option<Entity>
+	///
+	fn get_neighbor(param: &SystemParamItem<Self>, entity: Entity, offset: IVec2)
+		-> Option<Entity>;
+}
+
+/// A plugin that keeps [`AutoTileSprite`] entities in sync with their neighbors
+///
+/// Neighbors are found with the given [`AutoTileNeighbors`] implementation.
+pub struct AutoTileUpdatePlugin<N: AutoTileNeighbors>(PhantomData<N>);
+
+impl<N: AutoTileNeighbors> Default for AutoTileUpdatePlugin<N> {
+	fn default() -> Self { Self(PhantomData) }
+}
+
+impl<N: AutoTileNeighbors + Send + Sync> Plugin for AutoTileUpdatePlugin<N> {
+	fn build(&self, app: &mut App) { app.add_systems(Update, update_auto_tiles::<N>); }
+}
+
+/// The offset of each neighbor mapped to its bit in a [`NeighborMask`]
+const NEIGHBORS: [(IVec2, NeighborMask); 8] = [
+	(IVec2::new(0, 1), NeighborMask::NORTH),
+	(IVec2::new(1, 0), NeighborMask::EAST),
+	(IVec2::new(0, -1), NeighborMask::SOUTH),
+	(IVec2::new(-1, 0), NeighborMask::WEST),
+	(IVec2::new(1, 1), NeighborMask::NORTH_EAST),
+	(IVec2::new(1, -1), NeighborMask::SOUTH_EAST),
+	(IVec2::new(-1, -1), NeighborMask::SOUTH_WEST),
+	(IVec2::new(-1, 1), NeighborMask::NORTH_WEST),
+];
+
+/// The auto tile entities that were added or changed
+type ChangedAutoTiles<'w, 's> =
+	Query<'w, 's, Entity, Or<(Changed<AutoTileId>, Added<AutoTileSprite>)>>;
+
+/// The auto tile entities whose sprites are kept in sync with their neighbors
+type AutoTileSprites<'w, 's> = Query<
+	'w,
+	's,
+	(
+		&'static AutoTileId,
+		&'static mut AutoTileSprite,
+		&'static mut TextureAtlasSprite,
+	),
+>;
+
+/// System that updates the sprite of every [`AutoTileSprite`] affected by an auto tile being
+/// added, changed, or removed
+///
+/// Only the changed tiles and their neighbors are recomputed. The neighbors of each tile are
+/// remembered from its last update so that the neighbors of a removed tile can still be found
+/// once it's gone from the tilemap.
+pub fn update_auto_tiles<N: AutoTileNeighbors>(
+	neighbors: StaticSystemParam<N>,
+	tilesets: Tilesets,
+	mut removed: RemovedComponents<AutoTileId>,
+	mut index: Local<HashMap<Entity, Vec<Entity>>>,
+	ids: Query<&AutoTileId>,
+	mut tiles: ParamSet<(ChangedAutoTiles, AutoTileSprites)>,
+) {
+	let mut dirty = HashSet::new();
+	for entity in removed.read() {
+		dirty.extend(index.remove(&entity).unwrap_or_default());
+	}
+	for entity in tiles.p0().iter() {
+		dirty.insert(entity);
+		dirty.extend(index.get(&entity).into_iter().flatten().copied());
+		dirty.extend(
+			NEIGHBORS
+				.iter()
+				.filter_map(|(offset, ..)| N::get_neighbor(&neighbors, entity, *offset)),
+		);
+	}
+
+	let mut tiles = tiles.p1();
+	for entity in dirty {
+		let Ok((id, mut auto_sprite, mut sprite)) = tiles.get_mut(entity) else {
+			continue;
+		};
+		let found = NEIGHBORS
+			.iter()
+			.filter_map(|(offset, bit)| Some((N::get_neighbor(&neighbors, entity, *offset)?, *bit)))
+			.collect::<Vec<_>>();
+		let mask = found
+			.iter()
+			.filter(|(neighbor, ..)| {
+				ids.get(*neighbor)
+					.map_or(false, |neighbor_id| neighbor_id == id)
+			})
+			.fold(NeighborMask::NONE, |mask, (.., bit)| mask.with(*bit, true));
+		index.insert(
+			entity,
+			found.into_iter().map(|(neighbor, ..)| neighbor).collect(),
+		);
+
+		let Some(tileset) = tilesets.get_by_id(&id.tileset_id) else {
+			continue;
+		};
+		let Some(name) = tileset.get_tile_name(&id.group_id) else {
+			continue;
+		};
+		if auto_sprite.neighbors != mask {
+			auto_sprite.neighbors = mask;
+		}
+		// Keep the current variant if it still matches to avoid swapping variants needlessly
+		if tileset.is_auto_variant(name, &sprite.index, &AutoTileRule::from(mask)) {
+			continue;
+		}
+		if let Some(index) = tileset.resolve_auto_tile(name, mask) {
+			sprite.index = index;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::{ecs::system::SystemParam, prelude::*};
+
+	use super::*;
+	use crate::{
+		prelude::{TileGroupId, Tileset, TilesetId},
+		tileset::fixtures::{asset_app, build_walls, edge_rule, FIXTURES},
+	};
+
+	#[derive(Resource, Default)]
+	struct Grid(HashMap<IVec2, Entity>);
+
+	#[derive(Component)]
+	struct Position(IVec2);
+
+	#[derive(SystemParam)]
+	struct GridNeighbors<'w, 's> {
+		grid: Res<'w, Grid>,
+		positions: Query<'w, 's, &'static Position>,
+	}
+
+	impl AutoTileNeighbors for GridNeighbors<'static, 'static> {
+		fn get_neighbor(
+			param: &SystemParamItem<Self>,
+			entity: Entity,
+			offset: IVec2,
+		) -> Option<Entity> {
+			let pos = param.positions.get(entity).ok()?.0;
+			param.grid.0.get(&(pos + offset)).copied()
+		}
+	}
+
+	/// Adds a tileset with an auto tile whose index is the mask of its matching edges
+	fn add_edge_tileset(app: &mut App) -> Handle<Tileset> {
+		let autos = (0..16)
+			.map(|edges| (edge_rule(edges), false, None))
+			.collect::<Vec<_>>();
+		let world = &mut app.world;
+		let tileset =
+			build_walls(&autos).into_asset(&mut world.resource_mut::<Assets<TextureAtlas>>());
+		let handle = world.resource_mut::<Assets<Tileset>>().add(tileset);
+		app.update();
+		app.update();
+		handle
+	}
+
+	fn spawn_tile(app: &mut App, pos: IVec2) -> Entity {
+		let entity = app
+			.world
+			.spawn((
+				Position(pos),
+				AutoTileId {
+					group_id: 0 as TileGroupId,
+					tileset_id: TilesetId(0),
+				},
+				AutoTileSprite::default(),
+				TextureAtlasSprite::default(),
+			))
+			.id();
+		app.world.resource_mut::<Grid>().0.insert(pos, entity);
+		entity
+	}
+
+	fn index(app: &App, entity: Entity) -> usize {
+		app.world.get::<TextureAtlasSprite>(entity).unwrap().index
+	}
+
+	#[test]
+	fn should_update_neighbors_of_removed_tile() {
+		let mut app = asset_app(FIXTURES);
+		app.init_resource::<Grid>()
+			.add_plugins(AutoTileUpdatePlugin::<GridNeighbors>::default());
+		let _handle = add_edge_tileset(&mut app);
+
+		let mut grid = HashMap::new();
+		for y in -1..=1 {
+			for x in -1..=1 {
+				let pos = IVec2::new(x, y);
+				grid.insert(pos, spawn_tile(&mut app, pos));
+			}
+		}
+		let far = spawn_tile(&mut app, IVec2::new(5, 5));
+		app.update();
+
+		// North, east, south, and west neighbors
+		let edges = |pos: IVec2| {
+			[(0, 1), (1, 0), (0, -1), (-1, 0)]
+				.into_iter()
+				.enumerate()
+				.filter(|(.., (x, y))| {
+					let neighbor = pos + IVec2::new(*x, *y);
+					neighbor.x.abs() <= 1 && neighbor.y.abs() <= 1 && neighbor != IVec2::ZERO
+				})
+				.fold(0, |mask, (bit, ..)| mask | 1 << bit)
+		};
+		assert_eq!(0b1111, index(&app, grid[&IVec2::ZERO]));
+		assert_eq!(0b0110, index(&app, grid[&IVec2::new(-1, 1)]));
+		assert_eq!(0, index(&app, far));
+
+		// Tiles that aren't neighbors of a change shouldn't be recomputed
+		app.world.get_mut::<TextureAtlasSprite>(far).unwrap().index = 99;
+		let center = grid.remove(&IVec2::ZERO).unwrap();
+		app.world.resource_mut::<Grid>().0.remove(&IVec2::ZERO);
+		app.world.despawn(center);
+		app.update();
+
+		for (pos, entity) in grid {
+			assert_eq!(edges(pos), index(&app, entity), "tile at {}", pos);
+		}
+		assert_eq!(99, index(&app, far));
+	}
+}
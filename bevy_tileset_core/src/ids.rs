@@ -1,12 +1,37 @@
+use bevy::reflect::Reflect;
 use serde::{Deserialize, Serialize};
+use std::{
+	fmt::{Display, Formatter},
+	num::ParseIntError,
+	str::FromStr,
+};
 
 /// An ID used to identify a [`Tileset`]
-pub type TilesetId = u8;
+///
+/// This is a distinct type (rather than a bare integer) so that it can't be confused with
+/// a tile's index or group ID. It is serialized as a plain integer, so tileset definitions
+/// can continue to write `id: 0`.
+#[derive(
+	Debug,
+	Default,
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Hash,
+	Deserialize,
+	Serialize,
+	Reflect,
+)]
+#[serde(transparent)]
+pub struct TilesetId(pub u32);
 /// An ID used to identify a tile in a [`Tileset`]
 pub type TileGroupId = u32;
 
 /// A struct used to identify a tile in a particular [`Tileset`]
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, Reflect)]
 pub struct TileId {
 	/// The specific index of a ruling in the list of rules for this auto tile
 	///
@@ -112,6 +137,32 @@ impl PartialTileId {
 	}
 }
 
+impl TilesetId {
+	/// Create a new tileset ID
+	pub const fn new(id: u32) -> Self { Self(id) }
+
+	/// Gets the underlying integer value of this ID
+	pub const fn get(self) -> u32 { self.0 }
+}
+
+impl From<u32> for TilesetId {
+	fn from(id: u32) -> Self { Self(id) }
+}
+
+impl From<TilesetId> for u32 {
+	fn from(id: TilesetId) -> Self { id.0 }
+}
+
+impl Display for TilesetId {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result { write!(f, "{}", self.0) }
+}
+
+impl FromStr for TilesetId {
+	type Err = ParseIntError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> { s.parse().map(Self) }
+}
+
 impl From<TileId> for PartialTileId {
 	fn from(id: TileId) -> Self {
 		id.partial()
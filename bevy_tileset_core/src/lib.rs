@@ -1,15 +1,26 @@
+pub mod animation;
 pub mod debug;
 mod ids;
 mod plugin;
+pub mod spawn;
 mod tileset;
 
 #[cfg(feature = "auto-tile")]
 pub mod auto;
 pub mod coords;
+#[cfg(feature = "bevy_ecs_tilemap")]
+pub mod tilemap;
 
 /// A collection of commonly used modules (import via `bevy_tileset_core::prelude::*`)
 pub mod prelude {
+	pub use super::animation::{
+		TileAnimation, TileAnimationFinished, TileAnimationSpeed, TileAnimationState,
+		TileAnimationSystem,
+	};
 	pub use super::ids::{PartialTileId, TileGroupId, TileId, TilesetId};
 	pub use super::plugin::TilesetPlugin;
+	pub use super::spawn::SpawnTile;
+	#[cfg(feature = "variants")]
+	pub use super::spawn::TileVariant;
 	pub use super::tileset::*;
 }
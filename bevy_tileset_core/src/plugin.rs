@@ -1,40 +1,105 @@
-use crate::tileset::{Tileset, TilesetAssetLoader, TilesetMap};
+use crate::{
+	animation::{
+		animate_tiles,
+		TileAnimationFinished,
+		TileAnimationSpeed,
+		TileAnimationState,
+		TileAnimationSystem,
+	},
+	ids::{TileId, TilesetId},
+	tileset::{
+		TileDefAssetLoader,
+		TileIndexHistory,
+		Tileset,
+		TilesetAssetLoader,
+		TilesetDefaults,
+		TilesetMap,
+	},
+};
 use bevy::prelude::*;
+use bevy_tileset_tiles::prelude::{TileData, TileDef, TileDefType, TileType};
 
 /// Plugin for setting up tilesets
 #[derive(Default)]
-pub struct TilesetPlugin {}
+pub struct TilesetPlugin {
+	/// Whether tilesets and their tiles are looked up by name regardless of their casing
+	///
+	/// Names are still stored (and displayed) with their original casing and exact matches
+	/// are always preferred. This applies to [`Tilesets`](crate::prelude::Tilesets) as well
+	/// as to the tile lookups of every loaded [`Tileset`].
+	pub case_insensitive: bool,
+	/// The atlas options used by tileset definitions that leave them unset
+	///
+	/// This allows options like the padding between tiles to be configured once for every
+	/// tileset loaded from a definition file rather than repeating them in each file.
+	pub defaults: TilesetDefaults,
+}
 
 impl Plugin for TilesetPlugin {
 	fn build(&self, app: &mut App) {
-		app.init_asset_loader::<TilesetAssetLoader>()
+		// The map must be inserted before the loaders are initialized so they can read its settings
+		app.insert_resource(TilesetMap::new(self.case_insensitive))
+			.insert_resource(self.defaults.clone())
+			.init_resource::<TileIndexHistory>()
+			.init_asset_loader::<TilesetAssetLoader>()
 			.init_asset::<Tileset>()
-			.init_resource::<TilesetMap>()
-			.add_systems(Update, tileset_event_sys);
+			.init_asset_loader::<TileDefAssetLoader>()
+			.init_asset::<TileDef>()
+			.register_type::<Tileset>()
+			.register_type::<TilesetId>()
+			.register_type::<TileId>()
+			.register_type::<TileDef>()
+			.register_type::<TileDefType>()
+			.register_type::<TileData>()
+			.register_type::<TileType>()
+			.init_resource::<TileAnimationSpeed>()
+			.init_resource::<TileAnimationState>()
+			.add_event::<TileAnimationFinished>()
+			.add_systems(Update, tileset_event_sys)
+			.add_systems(Update, animate_tiles.in_set(TileAnimationSystem));
+
+		#[cfg(feature = "zip")]
+		app.init_asset_loader::<crate::tileset::ZipTilesetLoader>();
+		#[cfg(feature = "aseprite")]
+		app.init_asset_loader::<crate::tileset::AsepriteTilesetLoader>();
+		#[cfg(feature = "ldtk")]
+		app.init_asset_loader::<crate::tileset::LdtkTilesetLoader>();
+		#[cfg(feature = "tiled")]
+		app.init_asset_loader::<crate::tileset::TsxTilesetLoader>();
 	}
 }
 
-/// System that registers/deregisters tilesets as they are loaded and unloaded
+/// System that registers/deregisters tilesets as they are loaded, reloaded, and unloaded
 fn tileset_event_sys(
 	mut event_reader: EventReader<AssetEvent<Tileset>>,
 	mut map: ResMut<TilesetMap>,
 	tilesets: Res<Assets<Tileset>>,
-	asset_server: ResMut<AssetServer>,
+	history: Res<TileIndexHistory>,
+	asset_server: Option<Res<AssetServer>>,
 ) {
 	for event in event_reader.read() {
 		match event {
 			AssetEvent::<Tileset>::Added { id } => {
-				if let Some(handle) = asset_server.get_id_handle(*id) {
-					if let Some(tileset) = tilesets.get(handle.clone()) {
-						map.register_tileset(tileset, &handle);
-					}
+				if let Some(tileset) = tilesets.get(*id) {
+					map.register_tileset(tileset, *id);
+				}
+				// The path is no longer known once the tileset is removed, so it's tracked now
+				let path = asset_server
+					.as_ref()
+					.and_then(|asset_server| asset_server.get_path(*id));
+				if let Some(path) = path {
+					history.track(*id, path.path());
 				}
 			},
-			AssetEvent::<Tileset>::Removed { id } => {
-				if let Some(handle) = asset_server.get_id_handle(*id) {
-					map.deregister_tileset(&handle);
+			AssetEvent::<Tileset>::Modified { id } => {
+				if let Some(tileset) = tilesets.get(*id) {
+					map.reregister_tileset(tileset, *id);
 				}
 			},
+			AssetEvent::<Tileset>::Removed { id } => {
+				map.deregister_tileset(*id);
+				history.forget(*id);
+			},
 			_ => {},
 		}
 	}
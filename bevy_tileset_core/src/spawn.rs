@@ -0,0 +1,257 @@
+//! Helpers for spawning tiles as sprites
+
+#[cfg(feature = "variants")]
+use bevy::prelude::Component;
+use bevy::{
+	ecs::system::EntityCommands,
+	prelude::{ChildBuilder, Commands, Entity, SpriteSheetBundle, Transform, UVec2, Vec2},
+};
+
+#[cfg(feature = "variants")]
+use bevy_tileset_tiles::prelude::SimpleTileType;
+use bevy_tileset_tiles::prelude::{AnimatedTileData, TileType};
+
+use crate::{
+	animation::TileAnimation,
+	prelude::{PartialTileId, TileIndex, Tileset, TilesetParent, Tilesets},
+};
+
+/// An extension trait for spawning tiles by name
+///
+/// This is implemented for both `Commands` and `ChildBuilder`, so tiles can be spawned at the
+/// top level or as children of another entity (such as a map or chunk).
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+/// fn spawn_grass(mut commands: Commands, tilesets: Tilesets) {
+/// 	let transform = Transform::from_xyz(16.0, 0.0, 0.0);
+/// 	if let Some(entity) = commands.spawn_tile(&tilesets, "Overworld", "Grass", transform) {
+/// 		// Attach more components to `entity`...
+/// 	}
+/// }
+/// ```
+pub trait SpawnTile {
+	/// Spawns a tile as a sprite
+	///
+	/// The components of the tile are inserted with [`Tileset::insert_tile`], so Animated tiles
+	/// are spawned along with a [`TileAnimation`] that plays them and Variant tiles are marked
+	/// with the variant that was chosen.
+	///
+	/// # Arguments
+	///
+	/// * `tilesets`: The loaded tilesets
+	/// * `tileset`: The name of the tileset
+	/// * `tile`: The name of the tile
+	/// * `transform`: The transform of the spawned sprite
+	///
+	/// returns: Option<Entity>
+	///
+	fn spawn_tile(
+		&mut self,
+		tilesets: &Tilesets,
+		tileset: &str,
+		tile: &str,
+		transform: Transform,
+	) -> Option<Entity> {
+		self.spawn_tileset_tile(tilesets.get_by_name(tileset)?, tile, transform)
+	}
+
+	/// Spawns a tile from the given tileset as a sprite
+	///
+	/// This is the same as [`SpawnTile::spawn_tile`] but for an already retrieved tileset.
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset containing the tile
+	/// * `tile`: The name of the tile
+	/// * `transform`: The transform of the spawned sprite
+	///
+	/// returns: Option<Entity>
+	///
+	fn spawn_tileset_tile(
+		&mut self,
+		tileset: &Tileset,
+		tile: &str,
+		transform: Transform,
+	) -> Option<Entity>;
+
+	/// Spawns a rectangular grid of the same tile
+	///
+	/// The cell at `(0, 0)` is placed at `transform`, with the grid extending along +X and +Y.
+	/// Each cell is spawned with [`SpawnTile::spawn_tileset_tile`], so Variant tiles choose a
+	/// variant per cell. To spawn the grid under a parent entity, call this on the
+	/// `ChildBuilder` of that entity.
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The tileset containing the tile
+	/// * `tile`: The name of the tile
+	/// * `size`: The number of columns and rows in the grid
+	/// * `cell_size`: The distance between neighboring cells (usually the tile size)
+	/// * `transform`: The transform of the first cell
+	///
+	/// returns: Option<Vec<Entity>>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	/// fn spawn_field(mut commands: Commands, tilesets: Tilesets) {
+	/// 	let Some(tileset) = tilesets.get_by_name("Overworld") else {
+	/// 		return;
+	/// 	};
+	/// 	commands.spawn(SpatialBundle::default()).with_children(|parent| {
+	/// 		let size = UVec2::new(16, 9);
+	/// 		parent.spawn_tile_grid(tileset, "Grass", size, tileset.tile_size(), Transform::IDENTITY);
+	/// 	});
+	/// }
+	/// ```
+	fn spawn_tile_grid(
+		&mut self,
+		tileset: &Tileset,
+		tile: &str,
+		size: UVec2,
+		cell_size: Vec2,
+		transform: Transform,
+	) -> Option<Vec<Entity>> {
+		tileset.get_tile_group_id(tile)?;
+		let mut entities = Vec::with_capacity((size.x * size.y) as usize);
+		for y in 0..size.y {
+			for x in 0..size.x {
+				let offset = Vec2::new(x as f32, y as f32) * cell_size;
+				let mut transform = transform;
+				transform.translation += offset.extend(0.0);
+				entities.extend(self.spawn_tileset_tile(tileset, tile, transform));
+			}
+		}
+		Some(entities)
+	}
+}
+
+impl<'w, 's> SpawnTile for Commands<'w, 's> {
+	fn spawn_tileset_tile(
+		&mut self,
+		tileset: &Tileset,
+		tile: &str,
+		transform: Transform,
+	) -> Option<Entity> {
+		insert_spawned_tile(self.spawn_empty(), tileset, tile, transform)
+	}
+}
+
+impl<'w, 's, 'a> SpawnTile for ChildBuilder<'w, 's, 'a> {
+	fn spawn_tileset_tile(
+		&mut self,
+		tileset: &Tileset,
+		tile: &str,
+		transform: Transform,
+	) -> Option<Entity> {
+		insert_spawned_tile(self.spawn_empty(), tileset, tile, transform)
+	}
+}
+
+/// Inserts a tile into a newly spawned entity, despawning the entity if the tile doesn't exist
+///
+/// This is shared by the [`SpawnTile`] implementations.
+fn insert_spawned_tile(
+	mut commands: EntityCommands,
+	tileset: &Tileset,
+	tile: &str,
+	transform: Transform,
+) -> Option<Entity> {
+	if tileset.insert_tile(&mut commands, tile).is_none() {
+		commands.despawn();
+		return None;
+	}
+	Some(commands.insert(transform).id())
+}
+
+/// A component marking a tile entity that was spawned from a Variant tile
+///
+/// This contains the index of the variant that was chosen for the entity, so the same variant
+/// can be restored later (e.g. with [`Tileset::select_tile_by_id`]).
+#[cfg(feature = "variants")]
+#[derive(Component, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TileVariant(pub usize);
+
+impl Tileset {
+	/// Inserts the components for displaying a tile into an entity
+	///
+	/// The components depend on the kind of tile:
+	///
+	/// * Standard tiles only get a `SpriteSheetBundle`
+	/// * Animated tiles also get a [`TileAnimation`] that plays them (starting at a point seeded
+	///   by the entity if the animation has a random phase)
+	/// * Variant tiles have a random variant chosen and also get a [`TileVariant`] marking it (as
+	///   well as a [`TileAnimation`] if that variant is animated)
+	/// * Auto tiles display the tile matching their default rule
+	///
+	/// Every tile is also given a [`TilesetParent`]. The sprite is created the same way as by
+	/// [`Tileset::tile_sprite`], so reference flips and trim anchors are applied.
+	///
+	/// # Arguments
+	///
+	/// * `commands`: The commands of the entity to insert into
+	/// * `name`: The name of the tile
+	///
+	/// returns: Option<TileIndex>
+	///
+	pub fn insert_tile(&self, commands: &mut EntityCommands, name: &str) -> Option<TileIndex> {
+		#[cfg(feature = "variants")]
+		let variant = self.select_variant_index(name, &mut rand::thread_rng());
+		#[cfg(not(feature = "variants"))]
+		let variant = None;
+		let id = PartialTileId {
+			#[cfg(feature = "auto-tile")]
+			auto_index: None,
+			#[cfg(feature = "variants")]
+			variant_index: variant,
+			group_id: *self.get_tile_group_id(name)?,
+		};
+		let (index, ..) = self.select_tile_by_id(id)?;
+
+		let sprite = self.tile_sprite_at(name, *index.base_index())?;
+		commands.insert((
+			SpriteSheetBundle {
+				sprite,
+				texture_atlas: self.atlas().clone(),
+				..Default::default()
+			},
+			TilesetParent(*self.id()),
+		));
+		if let TileIndex::Animated(start, end, speed) = index {
+			let data = self.get_animated_data(name, variant);
+			let mut animation = match data {
+				Some(data) => TileAnimation::from_animated(self, data),
+				None => TileAnimation::new(start, end, speed),
+			}
+			.with_tile(name);
+			if data.is_some_and(AnimatedTileData::random_phase) {
+				animation = animation.with_phase_seed(commands.id().to_bits());
+			}
+			commands.insert(animation);
+		}
+		#[cfg(feature = "variants")]
+		if let Some(variant) = variant {
+			commands.insert(TileVariant(variant));
+		}
+		Some(index)
+	}
+	/// Gets the data of the animation displayed by a tile (or by the given variant of a tile)
+	#[cfg_attr(not(feature = "variants"), allow(unused_variables))]
+	fn get_animated_data(&self, name: &str, variant: Option<usize>) -> Option<&AnimatedTileData> {
+		match self.get_tile_data(name)?.tile() {
+			TileType::Animated(anim) => Some(anim),
+			#[cfg(feature = "variants")]
+			TileType::Variant(variants) => match variants.get(variant?)?.tile() {
+				SimpleTileType::Animated(anim) => Some(anim),
+				SimpleTileType::Standard(..) => None,
+			},
+			_ => None,
+		}
+	}
+}
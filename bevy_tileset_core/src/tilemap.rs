@@ -0,0 +1,42 @@
+//! Integration with [`bevy_ecs_tilemap`]
+//!
+//! Only accessible with the `bevy_ecs_tilemap` feature enabled
+
+use bevy_ecs_tilemap::prelude::{TileTextureIndex, TilemapTexture, TilemapTileSize};
+
+use crate::prelude::Tileset;
+
+impl Tileset {
+	/// Creates a `TilemapTexture` using this tileset's atlas texture
+	///
+	/// `bevy_ecs_tilemap` computes a tile's position in the atlas from the texture's width, so
+	/// this should only be used with tilesets built without any padding, extrusion, or
+	/// power-of-two rounding.
+	pub fn to_tilemap_texture(&self) -> TilemapTexture {
+		TilemapTexture::Single(self.texture().clone())
+	}
+
+	/// Gets the size of this tileset's tiles as a `TilemapTileSize`
+	pub fn tilemap_tile_size(&self) -> TilemapTileSize {
+		let size = self.tile_size();
+		TilemapTileSize {
+			x: size.x,
+			y: size.y,
+		}
+	}
+
+	/// Gets the `TileTextureIndex` for a tile with the given name
+	///
+	/// This is the tile's base index (see [`Tileset::get_base_tile_index`]).
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile
+	///
+	/// returns: Option<TileTextureIndex>
+	///
+	pub fn get_tile_texture_index(&self, name: &str) -> Option<TileTextureIndex> {
+		let index = self.get_base_tile_index(name)?;
+		Some(TileTextureIndex(index as u32))
+	}
+}
@@ -0,0 +1,208 @@
+//! Loading tilesets bundled in zip archives
+//!
+//! Only accessible with the `zip` feature enabled
+
+use std::{
+	collections::HashMap,
+	io::{Cursor, Read},
+	path::{Component, Path, PathBuf},
+};
+
+use bevy::{
+	asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, LoadContext},
+	prelude::{FromWorld, World},
+	render::texture::CompressedImageFormats,
+};
+use zip::ZipArchive;
+
+use crate::{
+	prelude::{Tileset, TilesetDefaults, TilesetError},
+	tileset::asset::{
+		case_insensitive,
+		load_tileset,
+		parse_tileset_def,
+		supported_compressed_formats,
+		tile_index_history,
+		tileset_defaults,
+		TileIndexHistory,
+		TilesetSource,
+	},
+};
+
+/// The path of the tileset definition within an archive
+const TILESET_ENTRY: &str = "tileset.ron";
+
+/// An asset loader for tilesets bundled in a zip archive (`.tileset.zip`)
+///
+/// The archive must contain its tileset definition as `tileset.ron` at its root. The tile
+/// definitions and textures it references are then read from within the archive (relative
+/// to its root) without extracting anything to disk.
+///
+/// Only stored and deflated entries are supported, and every entry's checksum is verified.
+pub(crate) struct ZipTilesetLoader {
+	supported_compressed_formats: CompressedImageFormats,
+	case_insensitive: bool,
+	defaults: TilesetDefaults,
+	history: TileIndexHistory,
+}
+
+impl FromWorld for ZipTilesetLoader {
+	fn from_world(world: &mut World) -> Self {
+		Self {
+			supported_compressed_formats: supported_compressed_formats(world),
+			case_insensitive: case_insensitive(world),
+			defaults: tileset_defaults(world),
+			history: tile_index_history(world),
+		}
+	}
+}
+
+impl AssetLoader for ZipTilesetLoader {
+	type Asset = Tileset;
+	type Settings = ();
+	type Error = TilesetError;
+
+	fn load<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		_settings: &'a Self::Settings,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			reader.read_to_end(&mut bytes).await?;
+			let entries = read_archive(&bytes)?;
+
+			let definition = entries.get(Path::new(TILESET_ENTRY)).ok_or_else(|| {
+				TilesetError::InvalidArchive(format!("missing {:?} entry", TILESET_ENTRY))
+			})?;
+			let mut definition = parse_tileset_def(definition)?;
+			definition.apply_defaults(&self.defaults);
+
+			let mut tileset = load_tileset(
+				definition,
+				Path::new(""),
+				&TilesetSource::Archive(entries),
+				load_context,
+				self.supported_compressed_formats,
+				&self.history,
+			)
+			.await?;
+			tileset.set_case_insensitive(self.case_insensitive);
+			Ok(tileset)
+		})
+	}
+
+	fn extensions(&self) -> &[&str] { &["tileset.zip"] }
+}
+
+/// Read the (decompressed) file entries of a zip archive mapped by their normalized path
+fn read_archive(bytes: &[u8]) -> Result<HashMap<PathBuf, Vec<u8>>, TilesetError> {
+	let mut archive =
+		ZipArchive::new(Cursor::new(bytes)).map_err(|err| invalid(&err.to_string()))?;
+
+	let mut entries = HashMap::with_capacity(archive.len());
+	for index in 0..archive.len() {
+		let mut file = archive
+			.by_index(index)
+			.map_err(|err| invalid(&err.to_string()))?;
+		if file.is_dir() {
+			continue;
+		}
+
+		// Reading to the end also verifies the entry's checksum
+		let mut data = Vec::new();
+		file.read_to_end(&mut data)
+			.map_err(|err| invalid(&format!("could not read {:?}: {}", file.name(), err)))?;
+		entries.insert(normalize_path(Path::new(file.name())), data);
+	}
+
+	Ok(entries)
+}
+
+/// Normalize a relative path by resolving any `.` and `..` components
+pub(super) fn normalize_path(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::Normal(part) => normalized.push(part),
+			Component::ParentDir => {
+				normalized.pop();
+			},
+			_ => {},
+		}
+	}
+	normalized
+}
+
+fn invalid(message: &str) -> TilesetError { TilesetError::InvalidArchive(message.to_string()) }
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		io::{Cursor, Write},
+		path::{Path, PathBuf},
+	};
+
+	use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+	use super::{normalize_path, read_archive};
+
+	/// Build a zip archive from the given entries, deflating those marked as compressed
+	fn build_archive(files: &[(&str, &[u8], bool)]) -> Vec<u8> {
+		let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+		for (name, data, compress) in files {
+			let method = if *compress {
+				CompressionMethod::Deflated
+			} else {
+				CompressionMethod::Stored
+			};
+			let options = FileOptions::default().compression_method(method);
+			if let Some(name) = name.strip_suffix('/') {
+				writer.add_directory(name, options).unwrap();
+			} else {
+				writer.start_file(*name, options).unwrap();
+				writer.write_all(data).unwrap();
+			}
+		}
+		writer.finish().unwrap().into_inner()
+	}
+
+	#[test]
+	fn should_read_archive_entries() {
+		let archive = build_archive(&[
+			("tileset.ron", b"(id: 0, tiles: {})", false),
+			("tiles/", b"", false),
+			("tiles/grass.ron", b"(name: \"Grass\")", true),
+		]);
+		let entries = read_archive(&archive).unwrap();
+
+		assert_eq!(2, entries.len());
+		assert_eq!(
+			b"(id: 0, tiles: {})".as_slice(),
+			entries[Path::new("tileset.ron")].as_slice()
+		);
+		assert_eq!(
+			b"(name: \"Grass\")".as_slice(),
+			entries[Path::new("tiles/grass.ron")].as_slice()
+		);
+	}
+
+	#[test]
+	fn should_reject_corrupted_entries() {
+		let mut archive = build_archive(&[("tileset.ron", b"(id: 0, tiles: {})", false)]);
+		// Flip a byte of the stored data (which follows the 30 byte header and the name)
+		archive[30 + "tileset.ron".len()] ^= 0xFF;
+
+		assert!(read_archive(&archive).is_err());
+		assert!(read_archive(b"not a zip archive").is_err());
+	}
+
+	#[test]
+	fn should_normalize_paths() {
+		assert_eq!(
+			PathBuf::from("textures/grass.png"),
+			normalize_path(Path::new("./tiles/../textures/grass.png"))
+		);
+	}
+}
@@ -0,0 +1,359 @@
+//! Importing animated tiles from [Aseprite](https://www.aseprite.org/) files
+//!
+//! Only accessible with the `aseprite` feature enabled
+
+use std::collections::HashMap;
+
+use asefile::{AnimationDirection, AsepriteFile};
+use bevy::{
+	asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, Handle, LoadContext},
+	prelude::{FromWorld, Image, World},
+	render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+	utils::Uuid,
+};
+use bevy_tileset_tiles::prelude::{AnimatedTileHandle, AnimationMode, TileHandle};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	prelude::{TileGroupId, Tileset, TilesetBuilder, TilesetError, TilesetId},
+	tileset::asset::{case_insensitive, finalize_tileset, TilesetTextureStore},
+};
+
+/// An asset loader for Aseprite files (`.ase`/`.aseprite`)
+///
+/// The visible layers of each frame are flattened into a single image and the frames are
+/// combined into an animated tile named after the file (e.g. `"water"` for `water.aseprite`),
+/// using each frame's duration. Every tag in the file is added as an additional animated tile,
+/// named after the tag and following its animation direction.
+///
+/// Files are decoded with [`asefile`], so every color mode, blend mode, and layer opacity is
+/// applied when flattening the layers. The ID of the tileset is taken from
+/// [`AsepriteLoaderSettings::id`].
+pub(crate) struct AsepriteTilesetLoader {
+	case_insensitive: bool,
+}
+
+impl FromWorld for AsepriteTilesetLoader {
+	fn from_world(world: &mut World) -> Self {
+		Self {
+			case_insensitive: case_insensitive(world),
+		}
+	}
+}
+
+/// The settings for loading an Aseprite file as a tileset
+///
+/// Tilesets are registered by their ID, so every file should be given its own ID:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+/// fn load_water(asset_server: Res<AssetServer>) {
+/// 	let handle: Handle<Tileset> = asset_server.load_with_settings(
+/// 		"water.aseprite",
+/// 		|settings: &mut AsepriteLoaderSettings| {
+/// 			settings.id = TilesetId(5);
+/// 		},
+/// 	);
+/// }
+/// ```
+///
+/// Only accessible with the `aseprite` feature enabled
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AsepriteLoaderSettings {
+	/// The ID of the tileset
+	pub id: TilesetId,
+}
+
+/// A decoded Aseprite file
+struct AseFile {
+	width: u32,
+	height: u32,
+	/// The flattened RGBA pixels of each frame along with its duration (in milliseconds)
+	frames: Vec<(Vec<u8>, u32)>,
+	tags: Vec<AseTag>,
+}
+
+/// A named range of frames in an Aseprite file
+struct AseTag {
+	name: String,
+	from: usize,
+	to: usize,
+	direction: AnimationDirection,
+}
+
+impl AssetLoader for AsepriteTilesetLoader {
+	type Asset = Tileset;
+	type Settings = AsepriteLoaderSettings;
+	type Error = TilesetError;
+
+	fn load<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		settings: &'a Self::Settings,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			reader.read_to_end(&mut bytes).await?;
+			let ase = AseFile::parse(&bytes)?;
+
+			let name = load_context
+				.path()
+				.file_stem()
+				.map(|stem| stem.to_string_lossy().to_string())
+				.unwrap_or_else(|| Uuid::new_v4().hyphenated().to_string());
+
+			// === Create Frames === //
+			let mut images = HashMap::with_capacity(ase.frames.len());
+			let mut frames = Vec::with_capacity(ase.frames.len());
+			for (index, (pixels, duration)) in ase.frames.into_iter().enumerate() {
+				let image = Image::new(
+					Extent3d {
+						width: ase.width,
+						height: ase.height,
+						depth_or_array_layers: 1,
+					},
+					TextureDimension::D2,
+					pixels,
+					TextureFormat::Rgba8UnormSrgb,
+				);
+				let handle: Handle<Image> =
+					load_context.get_label_handle(format!("frame_{}", index));
+				images.insert(handle.clone(), image);
+				frames.push((handle, duration));
+			}
+
+			// === Build Tiles === //
+			let mut store = TilesetTextureStore {
+				load_context,
+				images,
+			};
+			let mut builder = TilesetBuilder::new(None);
+			builder.case_insensitive(self.case_insensitive);
+			let all_frames = (0..frames.len()).collect::<Vec<_>>();
+			let animations = std::iter::once((name.clone(), all_frames)).chain(
+				ase.tags
+					.iter()
+					.map(|tag| (tag.name.clone(), tag.frame_order())),
+			);
+			for (group_id, (tile_name, order)) in animations.enumerate() {
+				let mut animation = AnimatedTileHandle {
+					speed: 1.0,
+					frames: Vec::with_capacity(order.len()),
+					durations: Vec::with_capacity(order.len()),
+					mode: AnimationMode::Loop,
+					random_phase: false,
+				};
+				for index in order {
+					let (handle, duration) = frames.get(index).ok_or_else(|| {
+						TilesetError::InvalidAseprite(format!("unknown frame {}", index))
+					})?;
+					animation.frames.push(handle.clone());
+					animation.durations.push(Some(*duration as f32 / 1000.0));
+				}
+				let tile_handle = TileHandle::new_animated(tile_name, animation);
+				builder.add_tile(tile_handle, group_id as TileGroupId, &store)?;
+			}
+
+			let raw_tileset = builder.build(name, settings.id, &mut store)?;
+			Ok(finalize_tileset(raw_tileset, "atlas", load_context))
+		})
+	}
+
+	fn extensions(&self) -> &[&str] { &["ase", "aseprite"] }
+}
+
+impl AseTag {
+	/// Get the frame indices of this tag in playback order
+	fn frame_order(&self) -> Vec<usize> {
+		let forward = (self.from..=self.to).collect::<Vec<_>>();
+		match self.direction {
+			AnimationDirection::Forward => forward,
+			AnimationDirection::Reverse => forward.into_iter().rev().collect(),
+			AnimationDirection::PingPong => {
+				let back = forward
+					.iter()
+					.rev()
+					.skip(1)
+					.take(forward.len().saturating_sub(2));
+				forward.iter().chain(back).copied().collect()
+			},
+		}
+	}
+}
+
+impl AseFile {
+	/// Parse and flatten the frames of an Aseprite file
+	fn parse(bytes: &[u8]) -> Result<Self, TilesetError> {
+		let file = AsepriteFile::read(bytes)
+			.map_err(|err| TilesetError::InvalidAseprite(err.to_string()))?;
+		let (width, height) = (file.width(), file.height());
+		let invalid_size =
+			|| TilesetError::InvalidAseprite(format!("unsupported size {}x{}", width, height));
+
+		let frames = (0..file.num_frames())
+			.map(|index| {
+				let frame = file.frame(index);
+				(frame.image().into_raw(), frame.duration())
+			})
+			.collect();
+		let tags = (0..file.num_tags())
+			.map(|index| {
+				let tag = file.tag(index);
+				AseTag {
+					name: tag.name().to_string(),
+					from: tag.from_frame() as usize,
+					to: tag.to_frame() as usize,
+					direction: tag.animation_direction(),
+				}
+			})
+			.collect();
+
+		Ok(Self {
+			width: u32::try_from(width).map_err(|_| invalid_size())?,
+			height: u32::try_from(height).map_err(|_| invalid_size())?,
+			frames,
+			tags,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use asefile::AnimationDirection;
+	use bevy::{
+		asset::{AssetServer, Assets, LoadState},
+		prelude::Handle,
+	};
+	use bevy_tileset_tiles::prelude::TileType;
+
+	use super::{AseFile, AseTag, AsepriteLoaderSettings};
+	use crate::{
+		prelude::{Tileset, TilesetId},
+		tileset::fixtures::{asset_app, wait_for_load, FIXTURES},
+	};
+
+	/// Build a minimal 1x1 RGBA Aseprite file with two frames and a single tag
+	fn build_file() -> Vec<u8> {
+		fn chunk(chunk_type: u16, data: &[u8]) -> Vec<u8> {
+			let mut bytes = ((data.len() + 6) as u32).to_le_bytes().to_vec();
+			bytes.extend(chunk_type.to_le_bytes());
+			bytes.extend(data);
+			bytes
+		}
+		fn frame(duration: u16, chunks: &[Vec<u8>]) -> Vec<u8> {
+			let body = chunks.concat();
+			let mut bytes = ((body.len() + 16) as u32).to_le_bytes().to_vec();
+			bytes.extend(0xF1FAu16.to_le_bytes());
+			bytes.extend((chunks.len() as u16).to_le_bytes());
+			bytes.extend(duration.to_le_bytes());
+			bytes.extend([0, 0]);
+			bytes.extend((chunks.len() as u32).to_le_bytes());
+			bytes.extend(body);
+			bytes
+		}
+		fn cel(color: [u8; 4]) -> Vec<u8> {
+			let mut data = vec![0, 0, 0, 0, 0, 0, 255, 0, 0];
+			data.extend([0; 7]);
+			data.extend([1, 0, 1, 0]);
+			data.extend(color);
+			chunk(0x2005, &data)
+		}
+
+		let mut layer = vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 255, 0, 0, 0];
+		layer.extend([1, 0, b'A']);
+		let mut tags = vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+		tags.extend([0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+		tags.extend([4, 0]);
+		tags.extend(b"Idle");
+
+		let frames = [
+			frame(
+				100,
+				&[
+					chunk(0x2004, &layer),
+					chunk(0x2018, &tags),
+					cel([255, 0, 0, 255]),
+				],
+			),
+			frame(250, &[cel([0, 0, 255, 255])]),
+		]
+		.concat();
+
+		let mut header = vec![0u8; 128];
+		header[0..4].copy_from_slice(&((frames.len() + 128) as u32).to_le_bytes());
+		header[4..6].copy_from_slice(&0xA5E0u16.to_le_bytes());
+		header[6..8].copy_from_slice(&2u16.to_le_bytes());
+		header[8..10].copy_from_slice(&1u16.to_le_bytes());
+		header[10..12].copy_from_slice(&1u16.to_le_bytes());
+		header[12..14].copy_from_slice(&32u16.to_le_bytes());
+		header[14..18].copy_from_slice(&1u32.to_le_bytes());
+		[header, frames].concat()
+	}
+
+	#[test]
+	fn should_parse_frames_and_tags() {
+		let ase = AseFile::parse(&build_file()).unwrap();
+
+		assert_eq!((1, 1), (ase.width, ase.height));
+		assert_eq!(vec![255, 0, 0, 255], ase.frames[0].0);
+		assert_eq!(100, ase.frames[0].1);
+		assert_eq!(vec![0, 0, 255, 255], ase.frames[1].0);
+		assert_eq!(250, ase.frames[1].1);
+
+		assert_eq!("Idle", ase.tags[0].name);
+		assert_eq!(vec![0, 1], ase.tags[0].frame_order());
+	}
+
+	#[test]
+	fn should_order_ping_pong_frames() {
+		let tag = AseTag {
+			name: String::new(),
+			from: 0,
+			to: 3,
+			direction: AnimationDirection::PingPong,
+		};
+		assert_eq!(vec![0, 1, 2, 3, 2, 1], tag.frame_order());
+	}
+
+	#[test]
+	fn should_flatten_layers_of_real_file() {
+		// A file saved by Aseprite (taken from the `aseprite-loader` crate) with five layers,
+		// including one with reduced opacity and one using the difference blend mode
+		let ase = AseFile::parse(include_bytes!("../../tests/fixtures/combine.aseprite")).unwrap();
+
+		assert_eq!((128, 128), (ase.width, ase.height));
+		assert_eq!(1, ase.frames.len());
+		assert!(ase.tags.is_empty());
+
+		let (pixels, duration) = &ase.frames[0];
+		assert_eq!(100, *duration);
+		assert_eq!(128 * 128 * 4, pixels.len());
+		let pixel = |x: usize, y: usize| &pixels[(y * 128 + x) * 4..(y * 128 + x + 1) * 4];
+		assert_eq!(&[94, 113, 142, 255], pixel(0, 0));
+		assert_eq!(&[49, 48, 0, 255], pixel(64, 64));
+	}
+
+	#[test]
+	fn should_load_single_frame_file() {
+		let mut app = asset_app(FIXTURES);
+		let handle: Handle<Tileset> = app.world.resource::<AssetServer>().load_with_settings(
+			"combine.aseprite",
+			|settings: &mut AsepriteLoaderSettings| {
+				settings.id = TilesetId(4);
+			},
+		);
+
+		assert_eq!(LoadState::Loaded, wait_for_load(&mut app, &handle));
+		let tilesets = app.world.resource::<Assets<Tileset>>();
+		let tileset = tilesets.get(&handle).unwrap();
+		assert_eq!(&TilesetId(4), tileset.id());
+		let Some(TileType::Animated(animated)) =
+			tileset.get_tile_data("combine").map(|data| data.tile())
+		else {
+			panic!("expected an animated tile");
+		};
+		assert_eq!((0, 0), (animated.start(), animated.end()));
+	}
+}
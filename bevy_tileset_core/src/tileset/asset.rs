@@ -1,12 +1,21 @@
 use std::{
-	collections::{BTreeMap, HashMap},
+	collections::{BTreeMap, HashMap, HashSet},
 	path::{Path, PathBuf},
 	sync::{Arc, RwLock},
 };
 
+#[cfg(feature = "zip")]
+use bevy::asset::{io::AssetReaderError, ReadAssetBytesError};
+#[cfg(any(feature = "tiled", feature = "ldtk"))]
+use bevy::render::{
+	render_resource::{Extent3d, TextureDimension},
+	texture::TextureFormatPixelInfo,
+};
+#[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+use bevy::tasks::AsyncComputeTaskPool;
 use bevy::{
 	asset::{
-		io::Reader,
+		io::{AssetSourceId, Reader},
 		Asset,
 		AssetLoader,
 		AssetPath,
@@ -15,24 +24,51 @@ use bevy::{
 		Handle,
 		LoadContext,
 	},
-	prelude::{AssetId, FromWorld, World},
+	log::warn,
+	prelude::{AssetId, FromWorld, Resource, World},
+	reflect::Reflect,
 	render::{
+		render_resource::TextureFormat,
 		renderer::RenderDevice,
-		texture::{CompressedImageFormats, Image, ImageSampler, ImageType},
+		texture::{CompressedImageFormats, Image, ImageFilterMode, ImageSampler, ImageType},
 	},
 	utils::Uuid,
 };
 use bevy_tile_atlas::TextureStore;
-use bevy_tileset_tiles::prelude::{TileDef, TileHandle};
+#[cfg(feature = "auto-tile")]
+use bevy_tileset_tiles::prelude::AutoTileRuleset;
+use bevy_tileset_tiles::prelude::{TileDef, TileDefType, TileHandle, TileHandleType};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "zip")]
+use crate::tileset::archive::normalize_path;
 use crate::{
-	prelude::{TileGroupId, Tileset, TilesetBuilder, TilesetError, TilesetId},
-	tileset::load::{load_tile_handles, TextureLoader},
+	prelude::{RawTileset, TileGroupId, Tileset, TilesetBuilder, TilesetError, TilesetId},
+	tileset::{
+		load::{load_tile_handles, TextureLoader},
+		TilesetMap,
+	},
 };
 
+/// The file extensions supported by the [`TilesetAssetLoader`]
+#[cfg(not(feature = "gzip"))]
+const EXTENSIONS: &[&str] = &["ron"];
+/// The file extensions supported by the [`TilesetAssetLoader`]
+#[cfg(feature = "gzip")]
+const EXTENSIONS: &[&str] = &["ron", "ron.gz"];
+
+/// The file extensions supported by the [`TileDefAssetLoader`]
+#[cfg(not(feature = "gzip"))]
+const TILE_EXTENSIONS: &[&str] = &["tile.ron"];
+/// The file extensions supported by the [`TileDefAssetLoader`]
+#[cfg(feature = "gzip")]
+const TILE_EXTENSIONS: &[&str] = &["tile.ron", "tile.ron.gz"];
+
 pub struct TilesetAssetLoader {
 	supported_compressed_formats: CompressedImageFormats,
+	case_insensitive: bool,
+	defaults: TilesetDefaults,
+	history: TileIndexHistory,
 }
 
 #[derive(Default, Deserialize, Serialize)]
@@ -41,9 +77,284 @@ pub struct TilesetDef {
 	pub name: Option<String>,
 	/// The ID of the tileset
 	pub id: TilesetId,
-	/// The tiles in this tileset as a mapping of their group ID to the relative path to
-	/// their definition file
+	/// The tiles in this tileset as a mapping of their group ID to the path to their
+	/// definition file
+	///
+	/// Paths are relative to this file unless they are absolute (used verbatim) or name an asset
+	/// source (e.g. `"mods://tiles/grass.ron"`, relative to the root of that source).
+	#[serde(default)]
 	pub tiles: BTreeMap<TileGroupId, String>,
+	/// The tiles defined inline in this file as a mapping of their group ID to their definition
+	///
+	/// This allows an entire tileset to be written as a single document rather than a
+	/// directory of tile definition files. Inline tiles may be combined with those in `tiles`,
+	/// but their group IDs must not overlap.
+	///
+	/// # Examples
+	///
+	/// ```ron
+	/// (
+	/// 	id: 0,
+	/// 	max_columns: Some(8),
+	/// 	tile_defs: {
+	/// 		0: (
+	/// 			name: "Grass",
+	/// 			tile: Standard("tiles/grass.png"),
+	/// 		),
+	/// 	},
+	/// )
+	/// ```
+	#[serde(default)]
+	pub tile_defs: BTreeMap<TileGroupId, TileDef>,
+	/// The path to a manifest file listing additional tile definition files
+	///
+	/// The manifest is a RON list of paths, which are relative to the manifest itself (unless they
+	/// are absolute or name an asset source). Only the listed tiles are loaded, in listed order,
+	/// and they're given the group IDs following the highest one used by `tiles` and `tile_defs`.
+	/// This allows multiple tilesets to share the same set of tiles.
+	///
+	/// The manifest path itself is resolved like the paths in `tiles`.
+	///
+	/// # Examples
+	///
+	/// ```ron
+	/// // tiles/terrain.manifest.ron
+	/// [
+	/// 	"grass.ron",
+	/// 	"dirt.ron",
+	/// 	"../water/water.ron",
+	/// ]
+	/// ```
+	#[serde(default)]
+	pub manifest: Option<String>,
+	/// The names of the only tiles to load (if `None`, all tiles are loaded)
+	///
+	/// Names may be given with or without their namespace. Tiles that aren't included are
+	/// skipped before any of their textures are loaded.
+	#[serde(default)]
+	pub include: Option<Vec<String>>,
+	/// The names of tiles to skip (applied after `include`)
+	///
+	/// Names may be given with or without their namespace.
+	#[serde(default)]
+	pub exclude: Option<Vec<String>>,
+	/// The maximum number of columns in the generated atlas before wrapping
+	///
+	/// If `None`, the [`TilesetDefaults`] are used (which place all tiles in a single row unless
+	/// configured otherwise)
+	#[serde(default)]
+	pub max_columns: Option<usize>,
+	/// The maximum number of rows in the generated atlas
+	///
+	/// Only enforced when `max_columns` is also set
+	#[serde(default)]
+	pub max_rows: Option<usize>,
+	/// The transparent space between neighboring tiles in the generated atlas (in pixels)
+	///
+	/// If `None`, the [`TilesetDefaults`] are used
+	#[serde(default)]
+	pub padding: Option<u32>,
+	/// The amount each tile's edges are extruded into its surrounding space (in pixels)
+	///
+	/// If `None`, the [`TilesetDefaults`] are used
+	#[serde(default)]
+	pub extrusion: Option<u32>,
+	/// Whether the generated atlas dimensions are rounded up to the next power of two
+	///
+	/// If `None`, the [`TilesetDefaults`] are used
+	#[serde(default)]
+	pub power_of_two: Option<bool>,
+	/// How the atlas texture is sampled (see [`TilesetBuilder::filter_mode`])
+	///
+	/// If `None`, the app's default sampler is used (see Bevy's `ImagePlugin`)
+	#[serde(default)]
+	pub filter_mode: Option<ImageFilterMode>,
+	/// The color space the atlas texture is interpreted in (see [`TilesetBuilder::color_space`])
+	///
+	/// Default: `Srgb`
+	#[serde(default)]
+	pub color_space: ColorSpace,
+	/// Whether tiles smaller than the first tile are allowed (see
+	/// [`TilesetBuilder::allow_mixed_sizes`])
+	#[serde(default)]
+	pub allow_mixed_sizes: bool,
+	/// Whether fully-transparent borders are trimmed from tile textures (see
+	/// [`TilesetBuilder::trim`])
+	///
+	/// This doesn't shrink the atlas, since trimmed tiles still take up a full cell.
+	#[serde(default)]
+	pub trim: bool,
+	/// How to handle multiple tile definitions sharing the same name
+	#[serde(default)]
+	pub duplicate_names: DuplicateNamePolicy,
+	/// The order tiles are added to the atlas in, which determines their atlas indices
+	#[serde(default)]
+	pub order: TileOrder,
+	/// Whether a texture that can't be read or decoded fails the entire tileset
+	///
+	/// If `false`, the tiles using such a texture are left out of the tileset and a warning is
+	/// logged for each failed texture and skipped tile instead.
+	#[serde(default)]
+	pub strict: bool,
+	/// Namespaces mapped by the directory (relative to this file) containing the tiles they apply to
+	///
+	/// The names of tiles in these directories are prefixed with their namespace (e.g. a tile
+	/// named `"Grass"` in a `"terrain"` namespace becomes `"terrain:Grass"`), which avoids name
+	/// collisions when combining tiles from multiple sources. If multiple directories match,
+	/// the most specific one is used. References to unqualified tile names are resolved
+	/// within the referencing tile's namespace.
+	#[serde(default)]
+	pub namespaces: BTreeMap<String, String>,
+}
+
+impl TilesetDef {
+	/// Gets the namespace for the tile definition at the given path (relative to this file)
+	///
+	/// # Arguments
+	///
+	/// * `tile_path`: The path to the tile definition file
+	///
+	/// returns: Option<&str>
+	///
+	pub fn get_namespace(&self, tile_path: &str) -> Option<&str> {
+		self.namespaces
+			.iter()
+			.filter(|(dir, ..)| Path::new(tile_path).starts_with(dir))
+			.max_by_key(|(dir, ..)| Path::new(dir).components().count())
+			.map(|(.., namespace)| namespace.as_str())
+	}
+
+	/// Checks whether a tile should be loaded according to this definition's `include` and
+	/// `exclude` lists
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile (as written in its definition)
+	/// * `namespace`: The namespace of the tile, if any
+	///
+	/// returns: bool
+	///
+	pub fn includes_tile(&self, name: &str, namespace: Option<&str>) -> bool {
+		let qualified = namespace.map(|namespace| format!("{}:{}", namespace, name));
+		let matches = |names: &Vec<String>| {
+			names
+				.iter()
+				.any(|other| other == name || Some(other) == qualified.as_ref())
+		};
+		let included = match &self.include {
+			Some(include) => matches(include),
+			None => true,
+		};
+		included && !self.exclude.as_ref().is_some_and(matches)
+	}
+
+	/// Add the tiles listed in this definition's manifest to its `tiles`
+	///
+	/// Does nothing if this definition has no manifest.
+	///
+	/// # Arguments
+	///
+	/// * `bytes`: The contents of the manifest file
+	///
+	/// returns: Result<(), TilesetError>
+	///
+	pub(super) fn add_manifest_tiles(&mut self, bytes: &[u8]) -> Result<(), TilesetError> {
+		let Some(manifest) = &self.manifest else {
+			return Ok(());
+		};
+		let paths = ron::de::from_bytes::<Vec<String>>(bytes)
+			.map_err(|err| TilesetError::InvalidManifest(manifest.into(), err.to_string()))?;
+
+		let manifest = AssetPath::parse(manifest);
+		let dir = manifest.path().parent().unwrap_or_else(|| Path::new(""));
+		let first_id = self
+			.tiles
+			.keys()
+			.chain(self.tile_defs.keys())
+			.max()
+			.map_or(0, |id| id + 1);
+		for (group_id, path) in (first_id..).zip(paths) {
+			let tile_path = AssetPath::parse(&path);
+			let tile_path = match tile_path.source() {
+				AssetSourceId::Default => AssetPath::from(dir.join(tile_path.path()))
+					.with_source(manifest.source().clone_owned())
+					.to_string(),
+				_ => path,
+			};
+			self.tiles.insert(group_id, tile_path);
+		}
+		Ok(())
+	}
+}
+
+/// The policy for handling tile definitions that share a name with a previously defined tile
+///
+/// Regardless of the policy, a warning is logged for every duplicate found.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DuplicateNamePolicy {
+	/// Keep the first tile with the given name and skip any duplicates
+	FirstWins,
+	/// Add every tile, with the name referring to the last one added
+	#[default]
+	LastWins,
+	/// Fail the load with a [`TilesetError::DuplicateTileName`] error
+	Error,
+}
+
+/// The order tiles are added to the atlas in
+///
+/// Either order is independent of the filesystem, so the same tileset always produces the same
+/// atlas indices. When a tileset is reloaded, tiles that already existed keep their index
+/// regardless of the order: new tiles are appended and removed tiles leave an empty cell behind.
+/// A tile is only moved (to the end of the atlas) if a tile before it grew into its cells.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Reflect, Deserialize, Serialize)]
+pub enum TileOrder {
+	/// Tiles are added in order of their group ID
+	#[default]
+	GroupId,
+	/// Tiles are added in order of their name (ties are broken by their group ID)
+	///
+	/// This keeps atlas indices stable when group IDs are reassigned. When the tileset is first
+	/// loaded, a tile added or renamed since the last run may shift the indices of the tiles that
+	/// follow it (unlike when it's reloaded, as described above).
+	Name,
+}
+
+/// The color space the texels of the atlas texture are interpreted in
+///
+/// This only changes the texture format of the atlas (e.g. `Rgba8UnormSrgb` vs `Rgba8Unorm`), the
+/// texel data itself is left untouched.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Reflect, Deserialize, Serialize)]
+pub enum ColorSpace {
+	/// The texels are sRGB encoded and converted to linear when sampled
+	///
+	/// This matches how Bevy loads PNG textures for sprites, so it should be used for textures
+	/// authored in an image editor.
+	#[default]
+	Srgb,
+	/// The texels are already linear and sampled as-is
+	///
+	/// Use this for data textures or textures that were authored in linear space.
+	Linear,
+}
+
+impl ColorSpace {
+	/// Gets the variant of the given texture format in this color space
+	///
+	/// Formats that don't have both an sRGB and a linear variant are returned unchanged.
+	///
+	/// # Arguments
+	///
+	/// * `format`: The texture format to convert
+	///
+	/// returns: TextureFormat
+	///
+	pub fn apply(&self, format: TextureFormat) -> TextureFormat {
+		match self {
+			Self::Srgb => format.add_srgb_suffix(),
+			Self::Linear => format.remove_srgb_suffix(),
+		}
+	}
 }
 
 /// A struct that mimics a Bevy `AssetServer`
@@ -54,13 +365,13 @@ struct TilesetTextureLoader<'x, 'y> {
 	supported_compressed_formats: CompressedImageFormats,
 	load_context: &'x mut LoadContext<'y>,
 	/// The images that need to be loaded
-	bytes: Arc<RwLock<HashMap<AssetId<Image>, PathBuf>>>,
+	bytes: Arc<RwLock<HashMap<AssetId<Image>, AssetPath<'static>>>>,
 }
 
 /// A struct that mimics a Bevy `Assets<Texture>` resource by allowing get/add operations
-struct TilesetTextureStore<'x, 'y> {
-	load_context: &'x mut LoadContext<'y>,
-	images: HashMap<Handle<Image>, Image>,
+pub(super) struct TilesetTextureStore<'x, 'y> {
+	pub(super) load_context: &'x mut LoadContext<'y>,
+	pub(super) images: HashMap<Handle<Image>, Image>,
 }
 
 impl<'x, 'y> TextureLoader for TilesetTextureLoader<'x, 'y> {
@@ -70,11 +381,8 @@ impl<'x, 'y> TextureLoader for TilesetTextureLoader<'x, 'y> {
 			.load_context
 			// FIXME unwrap
 			.get_label_handle(asset_path.clone().to_string());
-		let asset_path = asset_path.path();
-		let path = asset_path.to_path_buf();
-
 		if let Ok(mut images) = self.bytes.try_write() {
-			images.insert(handle.id(), path);
+			images.insert(handle.id(), asset_path.into_owned());
 		}
 		handle
 	}
@@ -127,15 +435,153 @@ impl<'x, 'y> TextureStore for TilesetTextureStore<'x, 'y> {
 
 impl FromWorld for TilesetAssetLoader {
 	fn from_world(world: &mut World) -> Self {
-		let supported_compressed_formats = match world.get_resource::<RenderDevice>() {
-			Some(render_device) => CompressedImageFormats::from_features(render_device.features()),
-
-			None => CompressedImageFormats::all(),
-		};
 		Self {
-			supported_compressed_formats,
+			supported_compressed_formats: supported_compressed_formats(world),
+			case_insensitive: case_insensitive(world),
+			defaults: tileset_defaults(world),
+			history: tile_index_history(world),
+		}
+	}
+}
+
+/// The atlas index of the first cell of each tile in the loaded tilesets, mapped by their path
+///
+/// This is used to keep the indices of existing tiles stable when a tileset is reloaded. It's
+/// shared between the loaders and the [`TilesetPlugin`](crate::prelude::TilesetPlugin), which
+/// forgets the indices of a tileset once it's unloaded so that loading it again starts fresh.
+#[derive(Resource, Default, Clone)]
+pub(crate) struct TileIndexHistory(Arc<RwLock<TileIndexEntries>>);
+
+#[derive(Default)]
+struct TileIndexEntries {
+	/// The start index of each tile mapped by the path of its tileset
+	indices: HashMap<PathBuf, HashMap<String, usize>>,
+	/// The path of each loaded tileset
+	paths: HashMap<AssetId<Tileset>, PathBuf>,
+}
+
+impl TileIndexHistory {
+	/// Get the start index of each tile in the tileset last loaded from the given path
+	pub(super) fn get(&self, path: &Path) -> Option<HashMap<String, usize>> {
+		self.0.read().ok()?.indices.get(path).cloned()
+	}
+
+	/// Record the start index of each tile in a tileset loaded from the given path
+	pub(super) fn record(&self, path: &Path, indices: HashMap<String, usize>) {
+		if let Ok(mut history) = self.0.write() {
+			history.indices.insert(path.to_path_buf(), indices);
+		}
+	}
+
+	/// Associate a loaded tileset asset with the path its indices were recorded under
+	pub(crate) fn track(&self, asset_id: AssetId<Tileset>, path: &Path) {
+		if let Ok(mut history) = self.0.write() {
+			history.paths.insert(asset_id, path.to_path_buf());
+		}
+	}
+
+	/// Forget the recorded indices of an unloaded tileset asset
+	pub(crate) fn forget(&self, asset_id: AssetId<Tileset>) {
+		if let Ok(mut history) = self.0.write() {
+			if let Some(path) = history.paths.remove(&asset_id) {
+				history.indices.remove(&path);
+			}
+		}
+	}
+}
+
+/// Get the start index of each tile in a tileset (see [`TileIndexHistory`])
+fn start_indices(tileset: &Tileset) -> HashMap<String, usize> {
+	let mut indices = HashMap::<String, usize>::new();
+	for (index, id) in tileset.tile_indices.iter() {
+		if let Some(name) = tileset.tile_names.get(&id.group_id) {
+			indices
+				.entry(name.clone())
+				.and_modify(|start| *start = (*start).min(*index))
+				.or_insert(*index);
 		}
 	}
+	indices
+}
+
+/// Get the [`TileIndexHistory`] shared by the loaders
+///
+/// This is inserted by the [`TilesetPlugin`](crate::prelude::TilesetPlugin).
+pub(super) fn tile_index_history(world: &World) -> TileIndexHistory {
+	world
+		.get_resource::<TileIndexHistory>()
+		.cloned()
+		.unwrap_or_default()
+}
+
+/// Get the compressed image formats supported by the current render device (if any)
+pub(super) fn supported_compressed_formats(world: &World) -> CompressedImageFormats {
+	match world.get_resource::<RenderDevice>() {
+		Some(render_device) => CompressedImageFormats::from_features(render_device.features()),
+
+		None => CompressedImageFormats::all(),
+	}
+}
+
+/// The atlas options used by tileset definitions that leave them unset
+///
+/// These are configured once for the entire app through
+/// [`TilesetPlugin::defaults`](crate::prelude::TilesetPlugin::defaults).
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct TilesetDefaults {
+	/// The maximum number of columns in the generated atlas before wrapping
+	///
+	/// If `None`, all tiles are placed in a single row
+	pub max_columns: Option<usize>,
+	/// The transparent space between neighboring tiles in the generated atlas (in pixels)
+	pub padding: u32,
+	/// The amount each tile's edges are extruded into its surrounding space (in pixels)
+	pub extrusion: u32,
+	/// Whether the generated atlas dimensions are rounded up to the next power of two
+	pub power_of_two: bool,
+}
+
+impl TilesetDef {
+	/// Fill in the atlas options this definition leaves unset from the given defaults
+	///
+	/// # Arguments
+	///
+	/// * `defaults`: The defaults to apply
+	///
+	pub fn apply_defaults(&mut self, defaults: &TilesetDefaults) {
+		self.max_columns = self.max_columns.or(defaults.max_columns);
+		self.padding.get_or_insert(defaults.padding);
+		self.extrusion.get_or_insert(defaults.extrusion);
+		self.power_of_two.get_or_insert(defaults.power_of_two);
+	}
+}
+
+/// Parse a tileset definition from its RON representation
+///
+/// Optional fields may be written without wrapping them in `Some(...)` (e.g. `padding: 2`).
+pub(super) fn parse_tileset_def(bytes: &[u8]) -> Result<TilesetDef, TilesetError> {
+	Ok(ron::Options::default()
+		.with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+		.from_bytes(bytes)?)
+}
+
+/// Get the [`TilesetDefaults`] applied to loaded tileset definitions
+///
+/// This is configured by the [`TilesetPlugin`](crate::prelude::TilesetPlugin).
+pub(super) fn tileset_defaults(world: &World) -> TilesetDefaults {
+	world
+		.get_resource::<TilesetDefaults>()
+		.cloned()
+		.unwrap_or_default()
+}
+
+/// Get whether loaded tilesets should look up their tiles regardless of casing
+///
+/// This is configured by the [`TilesetPlugin`](crate::prelude::TilesetPlugin).
+pub(super) fn case_insensitive(world: &World) -> bool {
+	world
+		.get_resource::<TilesetMap>()
+		.map_or(false, TilesetMap::is_case_insensitive)
 }
 
 impl AssetLoader for TilesetAssetLoader {
@@ -153,152 +599,598 @@ impl AssetLoader for TilesetAssetLoader {
 			let mut bytes = Vec::new();
 			reader.read_to_end(&mut bytes).await?;
 
-			let definition = ron::de::from_bytes::<TilesetDef>(&bytes)?;
-
-			// === Load Handles === //
-			let mut loader = TilesetTextureLoader {
-				supported_compressed_formats: self.supported_compressed_formats,
-				bytes: Arc::new(RwLock::new(HashMap::new())),
-				load_context,
+			#[cfg(feature = "gzip")]
+			let bytes = match load_context.path().extension().and_then(|ext| ext.to_str()) {
+				Some("gz") => decompress(load_context.path(), &bytes)?,
+				_ => bytes,
 			};
 
-			// FIXME
-			let mut tile_defs: Vec<TileDef> = vec![];
-			for (.., tile_path) in definition.tiles.iter() {
-				let path = tile_path;
-				let path = if let Some(parent) = loader.load_context.path().parent() {
-					parent.join(path)
-				} else {
-					Path::new(&path).to_path_buf()
-				};
-				let bytes = loader
-					.load_context
-					.read_asset_bytes(path)
-					.await
-					.map_err(|err| TilesetError::ReadAssetBytesError(err))?;
-				let def = ron::de::from_bytes::<TileDef>(&bytes)
-					.map_err(|err| TilesetError::InvalidDefinition(err))?;
-				tile_defs.push(def);
-			}
-			let handles = load_tile_handles(tile_defs, &mut loader);
-			let tile_handles: Vec<(TileGroupId, TileHandle)> = definition
-				.tiles
-				.iter()
-				.map(|(id, ..)| *id)
-				.zip(handles.into_iter().map(|handle| handle))
-				.collect();
-
-			// === Build Tiles === //
-			//let images = loader.collect_images().await?;
-			let images = loader.bytes.read().unwrap().clone();
-			let mut image_map = vec![];
-			for (id, path) in images.into_iter() {
-				let image =
-					load_image(load_context, id, path, self.supported_compressed_formats).await;
-				image_map.push(image);
-			}
-			let images = image_map
-				.into_iter()
-				.filter_map(|x| x.ok())
-				// TODO not sure about the Weak Handle here
-				.map(|(asset_id, image)| (Handle::Weak(asset_id), image))
-				.collect();
-			let mut store = TilesetTextureStore {
+			let mut definition = parse_tileset_def(&bytes)?;
+			definition.apply_defaults(&self.defaults);
+
+			let base = load_context
+				.path()
+				.parent()
+				.map(Path::to_path_buf)
+				.unwrap_or_default();
+			let mut tileset = load_tileset(
+				definition,
+				&base,
+				&TilesetSource::Assets,
 				load_context,
-				images,
-			};
+				self.supported_compressed_formats,
+				&self.history,
+			)
+			.await?;
+			tileset.set_case_insensitive(self.case_insensitive);
+			Ok(tileset)
+		})
+	}
+
+	fn extensions(&self) -> &[&str] { EXTENSIONS }
+}
+
+/// An asset loader for individual tile definitions (`.tile.ron`)
+///
+/// This allows a single [`TileDef`] to be loaded (and hot-reloaded) through the `AssetServer`,
+/// such as with `asset_server.load::<TileDef>("tiles/grass.tile.ron")`. Tilesets may still
+/// reference these files directly, since the suffix is ignored when parsing tile definitions.
+#[derive(Default)]
+pub struct TileDefAssetLoader;
+
+impl AssetLoader for TileDefAssetLoader {
+	type Asset = TileDef;
+	type Settings = ();
+	type Error = TilesetError;
 
-			let mut builder = TilesetBuilder::default();
-			for (group_id, tile_handle) in tile_handles {
-				builder.add_tile(tile_handle, group_id, &store)?;
+	fn load<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		_settings: &'a Self::Settings,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			reader.read_to_end(&mut bytes).await?;
+			#[allow(unused_mut)]
+			let mut tile_def = parse_tile_def(load_context.path(), &bytes)?;
+			#[cfg(feature = "auto-tile")]
+			if let Some(path) = ruleset_path(load_context.path(), &tile_def) {
+				let bytes = TilesetSource::Assets.read(load_context, &path).await?;
+				resolve_ruleset(&mut tile_def, path.path(), &bytes)?;
 			}
+			Ok(tile_def)
+		})
+	}
 
-			// === Create Raw Tileset === //
-			let name = definition
-				.name
-				.unwrap_or_else(|| Uuid::new_v4().hyphenated().to_string());
-			let raw_tileset = builder.build(name, definition.id, &mut store)?;
-
-			// === Finalize Tileset === //
-			let texture = raw_tileset.atlas().texture.clone();
-			let atlas = load_context.add_labeled_asset("atlas".to_owned(), raw_tileset.atlas);
-			let tileset = Tileset {
-				id: raw_tileset.id,
-				name: raw_tileset.name,
-				tiles: raw_tileset.tiles,
-				size: raw_tileset.size,
-				tile_size: raw_tileset.tile_size,
-				tile_ids: raw_tileset.tile_ids,
-				tile_names: raw_tileset.tile_names,
-				tile_handles: raw_tileset.tile_handles,
-				tile_indices: raw_tileset.tile_indices,
-				atlas,
-				texture,
-			};
+	fn extensions(&self) -> &[&str] { TILE_EXTENSIONS }
+}
 
-			Ok(tileset)
+/// Where the files referenced by a tileset definition are read from
+pub(super) enum TilesetSource {
+	/// The asset source the tileset definition was loaded from
+	Assets,
+	/// The entries of an archive mapped by their normalized path
+	#[cfg(feature = "zip")]
+	Archive(HashMap<PathBuf, Vec<u8>>),
+}
+
+impl TilesetSource {
+	/// Read the bytes of the file at the given path
+	///
+	/// Archives ignore the asset source of the path, if any.
+	async fn read(
+		&self,
+		load_context: &mut LoadContext<'_>,
+		path: &AssetPath<'_>,
+	) -> Result<Vec<u8>, TilesetError> {
+		match self {
+			Self::Assets => load_context
+				.read_asset_bytes(path.clone_owned())
+				.await
+				.map_err(|err| TilesetError::ReadAssetBytesError(err)),
+			#[cfg(feature = "zip")]
+			Self::Archive(entries) => {
+				let path = path.path();
+				entries.get(&normalize_path(path)).cloned().ok_or_else(|| {
+					TilesetError::ReadAssetBytesError(ReadAssetBytesError::AssetReaderError(
+						AssetReaderError::NotFound(path.to_path_buf()),
+					))
+				})
+			},
+		}
+	}
+}
+
+/// Resolve the path of a tile definition file referenced by a tileset
+///
+/// Paths with an explicit asset source (e.g. `"mods://tiles/grass.ron"`) are relative to the
+/// root of that source, which allows tiles to be read from a directory registered with
+/// `App::register_asset_source` (such as a per-user content directory). Absolute paths are used
+/// verbatim. All other paths are relative to `base`.
+///
+/// # Arguments
+///
+/// * `base`: The directory relative paths are resolved against
+/// * `tile_path`: The path as written in the tileset definition
+///
+/// returns: AssetPath<'static>
+///
+pub(super) fn resolve_tile_path(base: &Path, tile_path: &str) -> AssetPath<'static> {
+	let path = AssetPath::parse(tile_path);
+	match path.source() {
+		AssetSourceId::Default => AssetPath::from(base.join(path.path())),
+		_ => path.into_owned(),
+	}
+}
+
+/// Load a tileset from its definition, reading its tiles and textures from the given source
+///
+/// Tile definition paths are relative to `base`.
+pub(super) async fn load_tileset(
+	mut definition: TilesetDef,
+	base: &Path,
+	source: &TilesetSource,
+	load_context: &mut LoadContext<'_>,
+	supported_compressed_formats: CompressedImageFormats,
+	history: &TileIndexHistory,
+) -> Result<Tileset, TilesetError> {
+	// === Load Manifest === //
+	if let Some(manifest) = &definition.manifest {
+		let bytes = source
+			.read(load_context, &resolve_tile_path(base, manifest))
+			.await?;
+		definition.add_manifest_tiles(&bytes)?;
+	}
+
+	// === Load Handles === //
+	let mut loader = TilesetTextureLoader {
+		supported_compressed_formats,
+		bytes: Arc::new(RwLock::new(HashMap::new())),
+		load_context,
+	};
+
+	let mut tile_files = Vec::with_capacity(definition.tiles.len());
+	for (.., tile_path) in definition.tiles.iter() {
+		let path = resolve_tile_path(base, tile_path);
+		let bytes = source.read(loader.load_context, &path).await?;
+		tile_files.push((path.path().to_path_buf(), bytes));
+	}
+	let tileset_path = loader.load_context.path().to_path_buf();
+	let tile_defs = parse_tile_defs(tile_files).await?;
+	#[allow(unused_mut)]
+	let mut entries = collect_tile_entries(&definition, tile_defs, &tileset_path)?;
+	#[cfg(feature = "auto-tile")]
+	for entry in entries.iter_mut() {
+		if let Some(path) = entry.ruleset_path() {
+			let bytes = source.read(loader.load_context, &path).await?;
+			entry.resolve_ruleset(path.path(), &bytes)?;
+		}
+	}
+	let handles = load_tile_handles(
+		entries.iter().map(|entry| entry.tile_def.clone()),
+		&mut loader,
+	)?;
+
+	// === Build Tiles === //
+	//let images = loader.collect_images().await?;
+	let images = loader.bytes.read().unwrap().clone();
+	let mut image_map = HashMap::with_capacity(images.len());
+	let mut failed = Vec::new();
+	let mut failed_ids = HashSet::new();
+	for (id, path) in images.into_iter() {
+		let image = match source.read(load_context, &path).await {
+			Ok(bytes) => decode_image(path.path(), &bytes, supported_compressed_formats),
+			Err(err) => Err(err),
+		};
+		match image {
+			// TODO not sure about the Weak Handle here
+			Ok(image) => {
+				image_map.insert(Handle::Weak(id), image);
+			},
+			Err(err) => {
+				failed.push((path.path().to_path_buf(), err));
+				failed_ids.insert(id);
+			},
+		}
+	}
+	report_failed_textures(failed, definition.strict)?;
+	let (entries, handles) = skip_failed_tiles(entries, handles, &failed_ids);
+	let images = image_map;
+	let mut store = TilesetTextureStore {
+		load_context,
+		images,
+	};
+
+	// === Create Raw Tileset === //
+	let previous = history.get(&tileset_path);
+	let raw_tileset = build_tileset(definition, entries, handles, &mut store, previous.as_ref())?;
+
+	// === Finalize Tileset === //
+	let tileset = finalize_tileset(raw_tileset, "atlas", load_context);
+	history.record(&tileset_path, start_indices(&tileset));
+	Ok(tileset)
+}
+
+/// A tile to be added to a tileset, along with where it was defined
+pub(super) struct TileEntry {
+	/// The group ID of the tile
+	group_id: TileGroupId,
+	/// The definition of the tile
+	pub(super) tile_def: TileDef,
+	/// The path of the file defining the tile
+	path: PathBuf,
+	/// The namespace the tile belongs to
+	namespace: Option<String>,
+}
+
+#[cfg(feature = "auto-tile")]
+impl TileEntry {
+	/// Gets the path of the auto tile rule set referenced by this tile, if any
+	pub(super) fn ruleset_path(&self) -> Option<AssetPath<'static>> {
+		ruleset_path(&self.path, &self.tile_def)
+	}
+
+	/// Replaces the rule set referenced by this tile with the auto tiles it defines
+	pub(super) fn resolve_ruleset(
+		&mut self,
+		path: &Path,
+		bytes: &[u8],
+	) -> Result<(), TilesetError> {
+		resolve_ruleset(&mut self.tile_def, path, bytes)
+	}
+}
+
+/// Get the path of the auto tile rule set referenced by a tile, if any
+///
+/// The path is relative to the file defining the tile, at `tile_path`.
+#[cfg(feature = "auto-tile")]
+pub(super) fn ruleset_path(tile_path: &Path, tile_def: &TileDef) -> Option<AssetPath<'static>> {
+	match &tile_def.tile {
+		TileDefType::AutoRuleset(def) => Some(resolve_tile_path(
+			tile_path.parent().unwrap_or_else(|| Path::new("")),
+			&def.ruleset,
+		)),
+		_ => None,
+	}
+}
+
+/// Turn a tile referencing an auto tile rule set into a regular auto tile
+///
+/// This fails if the rule set can't be parsed or the tile provides sub-tiles for rules that
+/// don't exist in it. Tiles not referencing a rule set are left unchanged.
+///
+/// # Arguments
+///
+/// * `tile_def`: The tile definition
+/// * `path`: The path of the rule set file
+/// * `bytes`: The contents of the rule set file
+///
+/// returns: Result<(), TilesetError>
+///
+#[cfg(feature = "auto-tile")]
+pub(super) fn resolve_ruleset(
+	tile_def: &mut TileDef,
+	path: &Path,
+	bytes: &[u8],
+) -> Result<(), TilesetError> {
+	let TileDefType::AutoRuleset(def) = &tile_def.tile else {
+		return Ok(());
+	};
+	let invalid = |err: String| TilesetError::InvalidRuleset(path.to_path_buf(), err);
+	let ruleset =
+		ron::de::from_bytes::<AutoTileRuleset>(bytes).map_err(|err| invalid(err.to_string()))?;
+	let autos = def
+		.resolve(&ruleset)
+		.map_err(|err| invalid(format!("tile {:?} has {}", tile_def.name, err)))?;
+	tile_def.tile = TileDefType::Auto(autos);
+	Ok(())
+}
+
+/// Combine the parsed tile definition files of a tileset with its inline tiles
+///
+/// The returned entries are sorted by their group ID. Inline tiles are attributed to the
+/// tileset file at `tileset_path`.
+///
+/// # Arguments
+///
+/// * `definition`: The tileset definition
+/// * `tile_defs`: The path and parsed definition of each file in `definition.tiles` (in order)
+/// * `tileset_path`: The path of the tileset definition file
+///
+/// returns: Result<Vec<TileEntry>, TilesetError>
+///
+pub(super) fn collect_tile_entries(
+	definition: &TilesetDef,
+	tile_defs: Vec<(PathBuf, TileDef)>,
+	tileset_path: &Path,
+) -> Result<Vec<TileEntry>, TilesetError> {
+	let mut entries = definition
+		.tiles
+		.iter()
+		.zip(tile_defs)
+		.map(|((group_id, tile_path), (path, tile_def))| TileEntry {
+			group_id: *group_id,
+			tile_def,
+			path,
+			namespace: definition.get_namespace(tile_path).map(str::to_string),
 		})
+		.collect::<Vec<_>>();
+	for (group_id, tile_def) in definition.tile_defs.iter() {
+		if definition.tiles.contains_key(group_id) {
+			return Err(TilesetError::TileAlreadyExists(*group_id));
+		}
+		entries.push(TileEntry {
+			group_id: *group_id,
+			tile_def: tile_def.clone(),
+			path: tileset_path.to_path_buf(),
+			namespace: None,
+		});
+	}
+	entries
+		.retain(|entry| definition.includes_tile(&entry.tile_def.name, entry.namespace.as_deref()));
+	match definition.order {
+		TileOrder::GroupId => entries.sort_by_key(|entry| entry.group_id),
+		TileOrder::Name => entries
+			.sort_by(|a, b| (&a.tile_def.name, a.group_id).cmp(&(&b.tile_def.name, b.group_id))),
+	}
+	Ok(entries)
+}
+
+/// Handle the textures of a tileset that could not be read or decoded
+///
+/// In strict mode, this returns an error: either the first texture that failed to decode or,
+/// if all of them are simply missing, a single error listing every missing texture. Otherwise,
+/// a warning is logged for each failed texture.
+///
+/// # Arguments
+///
+/// * `failed`: The path of each failed texture along with its error
+/// * `strict`: Whether any failed texture should fail the entire tileset
+///
+/// returns: Result<(), TilesetError>
+///
+pub(super) fn report_failed_textures(
+	failed: Vec<(PathBuf, TilesetError)>,
+	strict: bool,
+) -> Result<(), TilesetError> {
+	if failed.is_empty() {
+		return Ok(());
 	}
 
-	fn extensions(&self) -> &[&str] { &["ron"] }
+	if strict {
+		let mut missing = Vec::with_capacity(failed.len());
+		for (path, err) in failed {
+			match err {
+				TilesetError::ReadAssetBytesError(..) | TilesetError::IoError(..) => {
+					missing.push(path)
+				},
+				err => return Err(err),
+			}
+		}
+		// Report every missing texture at once rather than building an atlas with holes
+		missing.sort();
+		return Err(TilesetError::MissingTextures(missing));
+	}
+
+	for (path, err) in failed {
+		warn!("could not load tile texture {:?}: {}", path, err);
+	}
+	Ok(())
 }
 
-/*
-/// Get a `Vec` of ([`TileGroupId`], [`TileHandle`]) tuples
-async fn get_tile_handles<'x, 'y>(
-	loader: &'x mut TilesetTextureLoader<'x, 'y>,
-	tile_paths: &BTreeMap<TileGroupId, String>,
-) -> Result<Vec<(TileGroupId, TileHandle)>, TilesetError> {
-	IoTaskPool::get()
-		.scope(|scope| {
-			let bytes = tile_paths.iter().map(|(.., tile_path)| {
-				let path = tile_path;
-				let path = if let Some(parent) = loader.load_context.path().parent() {
-					parent.join(path)
-				} else {
-					Path::new(path).to_path_buf()
-				};
-				loader.load_context.read_asset_bytes(path)
-			});
-
-			for bytes in bytes {
-				scope.spawn(async move {
-					let bytes = bytes
-						.await
-						.map_err(|err| TilesetError::ReadAssetBytesError(err))?;
-					let def = ron::de::from_bytes::<TileDef>(&bytes)
-						.map_err(|err| TilesetError::InvalidDefinition(err))?;
-					Ok(def)
-				});
+/// Remove the tiles using any of the given textures, logging a warning for each
+///
+/// # Arguments
+///
+/// * `entries`: The tiles to add
+/// * `handles`: The loaded handles of each tile in `entries` (in order)
+/// * `failed`: The IDs of the textures that could not be loaded
+///
+/// returns: (Vec<TileEntry>, Vec<TileHandle>)
+///
+pub(super) fn skip_failed_tiles(
+	entries: Vec<TileEntry>,
+	handles: Vec<TileHandle>,
+	failed: &HashSet<AssetId<Image>>,
+) -> (Vec<TileEntry>, Vec<TileHandle>) {
+	if failed.is_empty() {
+		return (entries, handles);
+	}
+
+	entries
+		.into_iter()
+		.zip(handles)
+		.filter(|(entry, handle)| {
+			let is_valid = handle
+				.iter_handles()
+				.all(|handle| !failed.contains(&handle.id()));
+			if !is_valid {
+				warn!(
+					"skipping tile {:?} defined in {:?} since not all of its textures could be \
+					 loaded",
+					handle.name, entry.path
+				);
 			}
+			is_valid
 		})
-		.into_iter()
-		.filter_map(|tile_def: Result<TileDef, TilesetError>| tile_def.ok())
-		.collect::<Vec<_>>();
-	/*
-	let tile_defs = futures::future::join_all(
-		tile_paths
-			.iter()
-			.map(|(.., tile_path)| load_tile(&mut loader.load_context, tile_path)),
-	)
-	.await
-	.into_iter()
-	.filter_map(|tile_def| tile_def.ok())
-	.collect::<Vec<_>>();
-	*/
-	// FIXME
-	//let tile_defs = vec![];
+		.unzip()
+}
 
-	//let handles = load_tile_handles(tile_defs, loader);
-	let handles = vec![];
+/// Build the tiles of a tileset definition into a [`RawTileset`]
+///
+/// This applies the definition's atlas options, namespaces, and duplicate name policy.
+///
+/// # Arguments
+///
+/// * `definition`: The tileset definition
+/// * `entries`: The tiles to add (see [`collect_tile_entries`])
+/// * `handles`: The loaded handles of each tile in `entries` (in order)
+/// * `store`: The store containing every texture referenced by `handles`
+/// * `previous`: The start index of each tile in a previous build of this tileset, which are
+///   kept wherever possible
+///
+/// returns: Result<RawTileset, TilesetError>
+///
+pub(super) fn build_tileset<TStore: TextureStore>(
+	definition: TilesetDef,
+	entries: Vec<TileEntry>,
+	handles: Vec<TileHandle>,
+	store: &mut TStore,
+	previous: Option<&HashMap<String, usize>>,
+) -> Result<RawTileset, TilesetError> {
+	let mut builder = TilesetBuilder::new(definition.max_columns);
+	builder
+		.max_rows(definition.max_rows)
+		.padding(definition.padding.unwrap_or_default())
+		.extrusion(definition.extrusion.unwrap_or_default())
+		.power_of_two(definition.power_of_two.unwrap_or_default())
+		.filter_mode(definition.filter_mode)
+		.color_space(definition.color_space)
+		.allow_mixed_sizes(definition.allow_mixed_sizes)
+		.trim(definition.trim)
+		.order(definition.order);
+	let mut tile_sources = HashMap::<String, PathBuf>::new();
+	let mut tiles = Vec::with_capacity(entries.len());
+	for (entry, mut tile_handle) in entries.into_iter().zip(handles) {
+		let TileEntry {
+			group_id,
+			mut tile_def,
+			path,
+			namespace,
+		} = entry;
+		if let Some(namespace) = namespace {
+			qualify_tile(namespace, &mut tile_handle, &mut tile_def);
+		}
+		if let Some(first) = tile_sources.get(&tile_handle.name) {
+			warn!(
+				"duplicate tile name {:?} defined in {:?} and {:?}",
+				tile_handle.name, first, path
+			);
+			match definition.duplicate_names {
+				DuplicateNamePolicy::FirstWins => continue,
+				DuplicateNamePolicy::LastWins => {},
+				DuplicateNamePolicy::Error => {
+					return Err(TilesetError::DuplicateTileName {
+						name: tile_handle.name,
+						first: first.clone(),
+						second: path,
+					});
+				},
+			}
+		}
+		tile_sources.insert(tile_handle.name.clone(), path);
+		tiles.push((group_id, tile_handle, tile_def));
+	}
 
-	Ok(tile_paths
-		.iter()
-		.map(|(id, ..)| *id)
-		.zip(handles.into_iter().map(|handle| handle))
-		.collect())
+	// Keep tiles from a previous build at the same index (unless a tile before them grew) and
+	// append any others
+	let (kept, added) = split_by_previous(tiles, previous);
+	let mut displaced = Vec::new();
+	for (start, tile) in kept {
+		if builder.next_index() > start {
+			displaced.push(tile);
+			continue;
+		}
+		builder.skip_to_index(start);
+		let (group_id, tile_handle, tile_def) = tile;
+		builder.add_tile(tile_handle, group_id, store)?;
+		builder.retain_tile_def(tile_def);
+	}
+	for (group_id, tile_handle, tile_def) in added.into_iter().chain(displaced) {
+		builder.add_tile(tile_handle, group_id, store)?;
+		builder.retain_tile_def(tile_def);
+	}
+
+	let name = definition
+		.name
+		.unwrap_or_else(|| Uuid::new_v4().hyphenated().to_string());
+	builder.build(name, definition.id, store)
+}
+
+/// A tile to be added to the atlas along with its definition
+type BuildTile = (TileGroupId, TileHandle, TileDef);
+
+/// Split tiles into those in a previous build of a tileset and those that are new
+///
+/// The previous tiles are sorted by the index they started at in the previous build, and are
+/// paired with that index. New tiles are kept in their given order.
+///
+/// # Arguments
+///
+/// * `tiles`: The tiles to split
+/// * `previous`: The start index of each tile in the previous build
+///
+/// returns: (Vec<(usize, BuildTile)>, Vec<BuildTile>)
+///
+fn split_by_previous(
+	tiles: Vec<BuildTile>,
+	previous: Option<&HashMap<String, usize>>,
+) -> (Vec<(usize, BuildTile)>, Vec<BuildTile>) {
+	let Some(previous) = previous else {
+		return (Vec::new(), tiles);
+	};
+
+	let mut kept = Vec::new();
+	let mut added = Vec::new();
+	for tile in tiles {
+		match previous.get(&tile.1.name) {
+			Some(start) => kept.push((*start, tile)),
+			None => added.push(tile),
+		}
+	}
+	kept.sort_by_key(|(start, ..)| *start);
+	(kept, added)
+}
+
+/// Prefix the name of a tile (and any unqualified reference it contains) with the given namespace
+fn qualify_tile(namespace: String, tile_handle: &mut TileHandle, tile_def: &mut TileDef) {
+	let qualify = |name: &mut String| {
+		if !name.contains(':') {
+			*name = format!("{}:{}", namespace, name);
+		}
+	};
+
+	tile_handle.name = format!("{}:{}", namespace, tile_handle.name);
+	tile_def.name = tile_handle.name.clone();
+	if let TileHandleType::Reference(reference) = &mut tile_handle.tile {
+		qualify(&mut reference.tile);
+	}
+	if let TileDefType::Reference(reference) = &mut tile_def.tile {
+		qualify(&mut reference.tile);
+	}
+}
+
+/// Convert a built [`RawTileset`] into a [`Tileset`], adding its atlas as a labeled asset
+pub(super) fn finalize_tileset(
+	raw_tileset: RawTileset,
+	atlas_label: &str,
+	load_context: &mut LoadContext,
+) -> Tileset {
+	let texture = raw_tileset.atlas().texture.clone();
+	let atlas = load_context.add_labeled_asset(atlas_label.to_owned(), raw_tileset.atlas);
+	Tileset {
+		id: raw_tileset.id,
+		name: raw_tileset.name,
+		tiles: raw_tileset.tiles,
+		size: raw_tileset.size,
+		tile_size: raw_tileset.tile_size,
+		tile_ids: raw_tileset.tile_ids,
+		tile_names: raw_tileset.tile_names,
+		tile_handles: raw_tileset.tile_handles,
+		tile_indices: raw_tileset.tile_indices,
+		frame_durations: raw_tileset.frame_durations,
+		trims: raw_tileset.trims,
+		atlas_efficiency: raw_tileset.atlas_efficiency,
+		tile_defs: raw_tileset.tile_defs,
+		case_insensitive: raw_tileset.case_insensitive,
+		options: raw_tileset.options,
+		atlas,
+		texture,
+		#[cfg(all(feature = "auto-tile", debug_assertions))]
+		unmatched_warnings: raw_tileset.unmatched_warnings,
+	}
 }
-	*/
 
 /// Load the tile definition at the given path and return its corresponding [TileDef]
 ///
@@ -315,8 +1207,70 @@ async fn load_tile<'x>(context: &mut LoadContext<'x>, path: &str) -> Result<Tile
 }
 */
 
+/// Parse a collection of tile definition files in parallel
+///
+/// With the `multi-threaded` feature, each file is parsed in its own task on the
+/// [`AsyncComputeTaskPool`]. Otherwise (or if the pool hasn't been initialized), the files are
+/// parsed in place. The returned definitions are in the same order as the given files
+/// so that the generated atlas indices remain stable between loads.
+async fn parse_tile_defs(
+	files: Vec<(PathBuf, Vec<u8>)>,
+) -> Result<Vec<(PathBuf, TileDef)>, TilesetError> {
+	let parse = |(path, bytes): (PathBuf, Vec<u8>)| {
+		let tile_def = parse_tile_def(&path, &bytes)?;
+		Ok((path, tile_def))
+	};
+	#[cfg(all(not(target_arch = "wasm32"), feature = "multi-threaded"))]
+	if let Some(pool) = AsyncComputeTaskPool::try_get() {
+		let tasks = files
+			.into_iter()
+			.map(|file| pool.spawn(async move { parse(file) }));
+		return futures::future::join_all(tasks).await.into_iter().collect();
+	}
+	files.into_iter().map(parse).collect()
+}
+
+/// Parse a tile definition file, choosing the format based on its extension
+///
+/// Files with a `json` or `yaml`/`yml` extension are parsed as such when their respective
+/// feature is enabled. All other files are treated as RON.
+///
+/// With the `gzip` feature, files with a `gz` extension (e.g. `my_tile.ron.gz`) are
+/// decompressed first and then parsed based on their remaining extension.
+pub(super) fn parse_tile_def(path: &Path, bytes: &[u8]) -> Result<TileDef, TilesetError> {
+	#[cfg(feature = "gzip")]
+	if path.extension().and_then(|ext| ext.to_str()) == Some("gz") {
+		let bytes = decompress(path, bytes)?;
+		return parse_tile_def(&path.with_extension(""), &bytes);
+	}
+
+	match path.extension().and_then(|ext| ext.to_str()) {
+		#[cfg(feature = "json")]
+		Some("json") => serde_json::from_slice::<TileDef>(bytes)
+			.map_err(|err| TilesetError::InvalidJsonDefinition(path.to_path_buf(), err)),
+		#[cfg(feature = "yaml")]
+		Some("yaml" | "yml") => serde_yaml::from_slice::<TileDef>(bytes)
+			.map_err(|err| TilesetError::InvalidYamlDefinition(path.to_path_buf(), err)),
+		_ => ron::de::from_bytes::<TileDef>(bytes)
+			.map_err(|err| TilesetError::InvalidDefinition(err)),
+	}
+}
+
+/// Decompress the gzip-compressed contents of the file at the given path
+#[cfg(feature = "gzip")]
+pub(super) fn decompress(path: &Path, bytes: &[u8]) -> Result<Vec<u8>, TilesetError> {
+	use std::io::Read;
+
+	let mut decompressed = Vec::new();
+	flate2::read::GzDecoder::new(bytes)
+		.read_to_end(&mut decompressed)
+		.map_err(|err| TilesetError::DecompressionError(path.to_path_buf(), err))?;
+	Ok(decompressed)
+}
+
 /// Load an image at the given path
-async fn load_image(
+#[cfg(any(feature = "tiled", feature = "ldtk"))]
+pub(super) async fn load_image(
 	context: &mut LoadContext<'_>,
 	id: AssetId<Image>,
 	path: PathBuf,
@@ -326,15 +1280,330 @@ async fn load_image(
 		.read_asset_bytes(path.clone())
 		.await
 		.map_err(|err| TilesetError::ReadAssetBytesError(err))?;
-	let path = path.as_path();
-	let ext = path.extension().unwrap().to_str().unwrap();
-	let img = Image::from_buffer(
-		&bytes,
+	let img = decode_image(&path, &bytes, supported_compressed_formats)?;
+	Ok((id, img))
+}
+
+/// Decode the bytes of the image at the given path
+pub(super) fn decode_image(
+	path: &Path,
+	bytes: &[u8],
+	supported_compressed_formats: CompressedImageFormats,
+) -> Result<Image, TilesetError> {
+	let ext = path
+		.extension()
+		.and_then(|ext| ext.to_str())
+		.ok_or_else(|| TilesetError::UnknownImageFormat(path.to_path_buf()))?;
+	Image::from_buffer(
+		bytes,
 		ImageType::Extension(ext),
 		supported_compressed_formats,
 		true,
 		ImageSampler::default(),
 	)
-	.map_err(|err| TilesetError::ImageError(err))?;
-	Ok((id, img))
+	.map_err(|err| TilesetError::ImageError(err))
+}
+
+/// Copy the given area of an image into a new image
+#[cfg(any(feature = "tiled", feature = "ldtk"))]
+pub(super) fn slice_image(image: &Image, x: u32, y: u32, width: u32, height: u32) -> Option<Image> {
+	if x + width > image.width() || y + height > image.height() {
+		return None;
+	}
+
+	let format = image.texture_descriptor.format;
+	let pixel_size = format.pixel_size();
+	let row_size = image.width() as usize * pixel_size;
+	let mut data = Vec::with_capacity(width as usize * height as usize * pixel_size);
+	for row in y..y + height {
+		let start = row as usize * row_size + x as usize * pixel_size;
+		data.extend_from_slice(image.data.get(start..start + width as usize * pixel_size)?);
+	}
+
+	Some(Image::new(
+		Extent3d {
+			width,
+			height,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		data,
+		format,
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		collections::HashMap,
+		path::{Path, PathBuf},
+	};
+
+	use bevy::{
+		asset::{AssetId, AssetPath, Assets},
+		render::texture::Image,
+		utils::Uuid,
+	};
+	use bevy_tileset_tiles::prelude::{TileDef, TileHandle};
+
+	use super::{
+		build_tileset,
+		collect_tile_entries,
+		parse_tileset_def,
+		resolve_tile_path,
+		TileEntry,
+		TileIndexHistory,
+		TileOrder,
+		TilesetDef,
+		TilesetDefaults,
+	};
+	use crate::{
+		prelude::{TileGroupId, Tileset},
+		tileset::fixtures::solid_image,
+	};
+
+	#[test]
+	fn should_resolve_tile_paths() {
+		let base = Path::new("tilesets");
+		assert_eq!(
+			AssetPath::from("tilesets/../tiles/grass.ron"),
+			resolve_tile_path(base, "../tiles/grass.ron")
+		);
+		assert_eq!(
+			AssetPath::from("mods://tiles/grass.ron"),
+			resolve_tile_path(base, "mods://tiles/grass.ron")
+		);
+		#[cfg(unix)]
+		assert_eq!(
+			Path::new("/usr/share/tiles/grass.ron"),
+			resolve_tile_path(base, "/usr/share/tiles/grass.ron").path()
+		);
+	}
+
+	#[test]
+	fn should_find_namespace() {
+		let definition = ron::from_str::<TilesetDef>(
+			r#"(
+				id: 0,
+				tiles: {},
+				namespaces: {
+					"tiles": "base",
+					"tiles/terrain": "terrain",
+				},
+			)"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			Some("terrain"),
+			definition.get_namespace("tiles/terrain/grass.ron")
+		);
+		assert_eq!(Some("base"), definition.get_namespace("tiles/wall.ron"));
+		assert_eq!(None, definition.get_namespace("other/wall.ron"));
+	}
+
+	#[test]
+	fn should_parse_inline_tile_defs() {
+		let definition = parse_tileset_def(
+			br#"(
+				id: 0,
+				max_columns: Some(4),
+				padding: 1,
+				tile_defs: {
+					0: (
+						name: "Grass",
+						tile: Standard("tiles/grass.png"),
+					),
+					1: (
+						name: "Dirt",
+						tile: Standard("tiles/dirt.png"),
+					),
+				},
+			)"#,
+		)
+		.unwrap();
+
+		assert!(definition.tiles.is_empty());
+		assert_eq!(2, definition.tile_defs.len());
+		assert_eq!("Dirt", definition.tile_defs[&1].name);
+	}
+
+	#[test]
+	fn should_order_tiles() {
+		let mut definition = parse_tileset_def(
+			br#"(
+				id: 0,
+				tile_defs: {
+					0: (
+						name: "Grass",
+						tile: Standard("tiles/grass.png"),
+					),
+					1: (
+						name: "Dirt",
+						tile: Standard("tiles/dirt.png"),
+					),
+				},
+			)"#,
+		)
+		.unwrap();
+		let names = |definition: &TilesetDef| {
+			collect_tile_entries(definition, Vec::new(), Path::new("tileset.ron"))
+				.unwrap()
+				.into_iter()
+				.map(|entry| entry.tile_def.name)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(vec!["Grass", "Dirt"], names(&definition));
+		definition.order = TileOrder::Name;
+		assert_eq!(vec!["Dirt", "Grass"], names(&definition));
+	}
+
+	#[test]
+	fn should_keep_indices_of_previous_tiles() {
+		let mut textures = Assets::<Image>::default();
+		let texture = textures.add(solid_image(2, 2));
+		let mut build = |names: &[&str], previous: Option<&HashMap<String, usize>>| {
+			let (entries, handles) = names
+				.iter()
+				.enumerate()
+				.map(|(group_id, name)| {
+					let tile_def = TileDef::new_standard(*name, format!("{}.png", name));
+					let entry = TileEntry {
+						group_id: group_id as TileGroupId,
+						tile_def,
+						path: PathBuf::from("tileset.ron"),
+						namespace: None,
+					};
+					(entry, TileHandle::new_standard(*name, texture.clone()))
+				})
+				.unzip();
+			let tileset = build_tileset(
+				TilesetDef::default(),
+				entries,
+				handles,
+				&mut textures,
+				previous,
+			)
+			.unwrap();
+			names
+				.iter()
+				.map(|name| (name.to_string(), tileset.get_base_tile_index(name).unwrap()))
+				.collect::<HashMap<_, _>>()
+		};
+
+		let previous = build(&["A", "B", "C"], None);
+		let indices = build(&["A", "D", "C"], Some(&previous));
+
+		assert_eq!(0, indices["A"]);
+		assert_eq!(2, indices["C"]);
+		assert_eq!(3, indices["D"]);
+	}
+
+	#[test]
+	fn should_forget_indices_of_unloaded_tilesets() {
+		let history = TileIndexHistory::default();
+		let path = Path::new("tilesets/world.ron");
+		let asset_id = AssetId::<Tileset>::Uuid {
+			uuid: Uuid::new_v4(),
+		};
+		history.record(path, HashMap::from([(String::from("Grass"), 3)]));
+		history.track(asset_id, path);
+
+		// Reloading keeps the indices
+		assert_eq!(
+			Some(&3),
+			history.get(path).as_ref().and_then(|map| map.get("Grass"))
+		);
+
+		history.forget(asset_id);
+		assert!(history.get(path).is_none());
+	}
+
+	#[test]
+	fn should_add_manifest_tiles() {
+		let mut definition = parse_tileset_def(
+			br#"(
+				id: 0,
+				tiles: {
+					3: "tiles/grass.ron",
+				},
+				manifest: "shared/terrain.manifest.ron",
+			)"#,
+		)
+		.unwrap();
+		definition
+			.add_manifest_tiles(br#"["dirt.ron", "../water/water.ron", "mods://lava.ron"]"#)
+			.unwrap();
+
+		let tiles = definition.tiles.into_iter().collect::<Vec<_>>();
+		assert_eq!(
+			vec![
+				(3, "tiles/grass.ron".to_string()),
+				(4, "shared/dirt.ron".to_string()),
+				(5, "shared/../water/water.ron".to_string()),
+				(6, "mods://lava.ron".to_string()),
+			],
+			tiles
+		);
+	}
+
+	#[test]
+	fn should_filter_tiles() {
+		let mut definition = parse_tileset_def(
+			br#"(
+				id: 0,
+				include: ["Grass", "base:Wall", "Dirt"],
+				exclude: ["Dirt"],
+			)"#,
+		)
+		.unwrap();
+
+		assert!(definition.includes_tile("Grass", None));
+		assert!(definition.includes_tile("Wall", Some("base")));
+		assert!(!definition.includes_tile("Wall", Some("other")));
+		assert!(!definition.includes_tile("Dirt", None));
+		assert!(!definition.includes_tile("Water", None));
+
+		definition.include = None;
+		assert!(definition.includes_tile("Water", None));
+		assert!(!definition.includes_tile("Dirt", None));
+	}
+
+	#[test]
+	fn should_apply_defaults_to_unset_options() {
+		let defaults = TilesetDefaults {
+			max_columns: Some(8),
+			padding: 2,
+			extrusion: 1,
+			power_of_two: true,
+		};
+		let mut definition =
+			parse_tileset_def(br#"(id: 0, padding: 0, power_of_two: false)"#).unwrap();
+		definition.apply_defaults(&defaults);
+
+		assert_eq!(Some(8), definition.max_columns);
+		assert_eq!(Some(0), definition.padding);
+		assert_eq!(Some(1), definition.extrusion);
+		assert_eq!(Some(false), definition.power_of_two);
+	}
+
+	#[cfg(feature = "gzip")]
+	#[test]
+	fn should_parse_compressed_tile_def() {
+		use std::{io::Write, path::Path};
+
+		use flate2::{write::GzEncoder, Compression};
+
+		use super::parse_tile_def;
+
+		let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+		encoder
+			.write_all(br#"(name: "My Tile", tile: Standard("my_tile.png"))"#)
+			.unwrap();
+		let bytes = encoder.finish().unwrap();
+
+		let tile_def = parse_tile_def(Path::new("tiles/my_tile.ron.gz"), &bytes).unwrap();
+		assert_eq!("My Tile", tile_def.name);
+	}
 }
@@ -0,0 +1,460 @@
+//! Layout of the generated atlas texture
+
+use std::collections::HashMap;
+
+use bevy::{
+	math::{Rect, Vec2},
+	prelude::{AssetId, Handle, Image},
+	reflect::Reflect,
+	render::{
+		render_resource::{Extent3d, TextureDimension, TextureFormat},
+		texture::{ImageSampler, TextureFormatPixelInfo},
+	},
+	sprite::{Anchor, TextureAtlas},
+};
+use bevy_tile_atlas::TextureStore;
+use serde::{Deserialize, Serialize};
+
+use crate::tileset::ColorSpace;
+
+/// Describes how tiles are arranged within the atlas texture
+///
+/// The atlas generated by `bevy_tile_atlas` packs its tiles edge-to-edge in a grid. This layout
+/// allows that grid to be spread out so that neighboring tiles are separated by transparent padding,
+/// and each tile's edges to be extruded outwards to surround it with copies of its border pixels.
+/// The texture may also be enlarged to power-of-two dimensions without moving any tiles.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct AtlasLayout {
+	/// The size of a single tile (in pixels)
+	tile_size: Vec2,
+	/// The number of columns in the grid
+	columns: usize,
+	/// The number of rows in the grid
+	rows: usize,
+	/// The transparent space between neighboring tiles (in pixels)
+	padding: u32,
+	/// The number of times each tile's border pixels are repeated outwards (in pixels)
+	extrusion: u32,
+	/// Whether the texture's dimensions are rounded up to the next power of two
+	power_of_two: bool,
+}
+
+/// A [`TextureStore`] that arranges the finished atlas texture according to an [`AtlasLayout`]
+/// before adding it to the underlying store
+pub(crate) struct LayoutTextureStore<'a, TStore: TextureStore> {
+	pub store: &'a mut TStore,
+	pub layout: AtlasLayout,
+	/// Textures resized to fit the tile size, used in place of the originals
+	pub fitted: &'a HashMap<AssetId<Image>, Image>,
+	/// The sampler given to the atlas texture
+	pub sampler: ImageSampler,
+	/// The color space the atlas texture is interpreted in
+	pub color_space: ColorSpace,
+}
+
+impl AtlasLayout {
+	/// Create a new layout for the given number of tiles
+	///
+	/// # Arguments
+	///
+	/// * `tile_size`: The size of a single tile
+	/// * `total`: The total number of tiles in the atlas
+	/// * `max_columns`: The maximum number of columns (or `None` for a single row)
+	/// * `padding`: The space between neighboring tiles
+	/// * `extrusion`: The amount each tile's edges are extruded
+	///
+	/// returns: AtlasLayout
+	///
+	pub fn new(
+		tile_size: Vec2,
+		total: usize,
+		max_columns: Option<usize>,
+		padding: u32,
+		extrusion: u32,
+	) -> Self {
+		let columns = max_columns.unwrap_or(total).max(1);
+		let rows = (total + columns - 1) / columns;
+		Self {
+			tile_size,
+			columns,
+			rows,
+			padding,
+			extrusion,
+			power_of_two: false,
+		}
+	}
+
+	/// Sets whether the texture's dimensions are rounded up to the next power of two
+	pub fn with_power_of_two(mut self, power_of_two: bool) -> Self {
+		self.power_of_two = power_of_two;
+		self
+	}
+
+	/// Returns true if tiles are packed edge-to-edge (i.e. the layout adds nothing to the base grid)
+	pub fn is_packed(&self) -> bool {
+		self.padding == 0 && self.extrusion == 0 && self.size() == self.content_size()
+	}
+
+	/// The total size of the atlas texture (in pixels)
+	pub fn size(&self) -> Vec2 {
+		let size = self.content_size();
+		if self.power_of_two {
+			Vec2::new(
+				(size.x as u32).next_power_of_two() as f32,
+				(size.y as u32).next_power_of_two() as f32,
+			)
+		} else {
+			size
+		}
+	}
+
+	/// The size of the area of the atlas texture covered by tiles (in pixels)
+	fn content_size(&self) -> Vec2 {
+		let grid = Vec2::new(self.columns as f32, self.rows as f32);
+		let gaps = (grid - Vec2::ONE).max(Vec2::ZERO);
+		self.cell_size() * grid + gaps * self.padding as f32
+	}
+
+	/// The size of a single tile including its extruded edges (in pixels)
+	fn cell_size(&self) -> Vec2 { self.tile_size + 2.0 * self.extrusion as f32 }
+
+	/// The area of the atlas texture covered by the tile at the given index
+	pub fn tile_rect(&self, index: usize) -> Rect {
+		let cell = Vec2::new((index % self.columns) as f32, (index / self.columns) as f32);
+		let min = cell * (self.cell_size() + self.padding as f32) + self.extrusion as f32;
+		Rect {
+			min,
+			max: min + self.tile_size,
+		}
+	}
+
+	/// Create a `TextureAtlas` for the given texture using this layout
+	pub fn texture_atlas(&self, texture: Handle<Image>) -> TextureAtlas {
+		let mut atlas = TextureAtlas::new_empty(texture, self.size());
+		for index in 0..self.columns * self.rows {
+			atlas.add_texture(self.tile_rect(index));
+		}
+		atlas
+	}
+
+	/// Arrange a packed grid texture according to this layout
+	///
+	/// # Arguments
+	///
+	/// * `packed`: The atlas texture with all tiles packed edge-to-edge
+	///
+	/// returns: Image
+	///
+	pub fn apply(&self, packed: Image) -> Image {
+		if self.is_packed() {
+			return packed;
+		}
+
+		let format = packed.texture_descriptor.format;
+		let pixel_size = format.pixel_size();
+		let size = self.size();
+		let extent = Extent3d {
+			width: size.x as u32,
+			height: size.y as u32,
+			depth_or_array_layers: 1,
+		};
+		let mut image = Image::new_fill(extent, TextureDimension::D2, &vec![0; pixel_size], format);
+		image.sampler = packed.sampler.clone();
+
+		let tile_width = self.tile_size.x as usize;
+		let tile_height = self.tile_size.y as usize;
+		let row_bytes = tile_width * pixel_size;
+		let packed_width = packed.width() as usize;
+		let image_width = image.width() as usize;
+		let extrusion = self.extrusion as usize;
+		let offset_of = |x: usize, y: usize| (y * image_width + x) * pixel_size;
+
+		for index in 0..self.columns * self.rows {
+			let (column, row) = (index % self.columns, index / self.columns);
+			let min = self.tile_rect(index).min;
+			let (x, y) = (min.x as usize, min.y as usize);
+
+			for offset in 0..tile_height {
+				let from = ((row * tile_height + offset) * packed_width + column * tile_width)
+					* pixel_size;
+				let to = offset_of(x, y + offset);
+				image.data[to..to + row_bytes]
+					.copy_from_slice(&packed.data[from..from + row_bytes]);
+
+				// Extrude the left and right edges
+				let (left, right) = (to, to + row_bytes - pixel_size);
+				for distance in 1..=extrusion {
+					let shift = distance * pixel_size;
+					image
+						.data
+						.copy_within(left..left + pixel_size, left - shift);
+					image
+						.data
+						.copy_within(right..right + pixel_size, right + shift);
+				}
+			}
+
+			// Extrude the top and bottom edges (including the already-extruded corners)
+			let span = row_bytes + 2 * extrusion * pixel_size;
+			let top = offset_of(x - extrusion, y);
+			let bottom = offset_of(x - extrusion, y + tile_height - 1);
+			for distance in 1..=extrusion {
+				image
+					.data
+					.copy_within(top..top + span, offset_of(x - extrusion, y - distance));
+				image.data.copy_within(
+					bottom..bottom + span,
+					offset_of(x - extrusion, y + tile_height - 1 + distance),
+				);
+			}
+		}
+
+		image
+	}
+}
+
+impl<'a, TStore: TextureStore> TextureStore for LayoutTextureStore<'a, TStore> {
+	fn add(&mut self, asset: Image) -> Handle<Image> {
+		let mut image = self.layout.apply(asset);
+		image.sampler = self.sampler.clone();
+		image.texture_descriptor.format = self.color_space.apply(image.texture_descriptor.format);
+		self.store.add(image)
+	}
+
+	fn get<H: Into<Handle<Image>>>(&self, handle: H) -> Option<&Image> {
+		let handle = handle.into();
+		match self.fitted.get(&handle.id()) {
+			Some(image) => Some(image),
+			None => self.store.get(handle),
+		}
+	}
+}
+
+/// Compute the ratio of pixels covered by tiles to the total pixels of an atlas texture
+///
+/// # Arguments
+///
+/// * `rects`: The area of the atlas texture covered by each cell, ordered by index
+/// * `indices`: The indices of the cells containing a tile (any other cells are empty)
+/// * `size`: The size of the atlas texture
+///
+/// returns: f32
+///
+pub(crate) fn atlas_efficiency<'a>(
+	rects: &[Rect],
+	indices: impl IntoIterator<Item = &'a usize>,
+	size: Vec2,
+) -> f32 {
+	let total = size.x * size.y;
+	if total <= 0.0 {
+		return 0.0;
+	}
+	let used: f32 = indices
+		.into_iter()
+		.filter_map(|index| rects.get(*index))
+		.map(|rect| {
+			let size = rect.size();
+			size.x * size.y
+		})
+		.sum();
+	used / total
+}
+
+/// The fully-transparent borders that were trimmed from a tile texture before it was packed
+///
+/// The atlas rect of a trimmed tile only covers its visible pixels. Use [`TileTrim::anchor`] to
+/// place a sprite of the trimmed tile where the untrimmed texture would have been.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect, Deserialize, Serialize)]
+pub struct TileTrim {
+	/// The position of the visible pixels within the original texture (in pixels)
+	pub offset: Vec2,
+	/// The size of the visible pixels (in pixels)
+	pub size: Vec2,
+	/// The size of the original texture (in pixels)
+	pub source_size: Vec2,
+}
+
+impl TileTrim {
+	/// Gets the sprite anchor that keeps the center of the original texture at the sprite's origin
+	pub fn anchor(&self) -> Anchor {
+		let center = self.source_size / 2.0 - self.offset;
+		let anchor = (center - self.size / 2.0) / self.size;
+		Anchor::Custom(Vec2::new(anchor.x, -anchor.y))
+	}
+}
+
+/// Crop the fully-transparent rows and columns from the borders of a texture
+///
+/// Only 8-bit RGBA and BGRA textures can be trimmed. Returns `None` if the texture can't be
+/// trimmed, has no transparent borders, or is fully transparent.
+pub(crate) fn trim_texture(texture: &Image) -> Option<(Image, TileTrim)> {
+	let format = texture.texture_descriptor.format;
+	if !matches!(
+		format.remove_srgb_suffix(),
+		TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm
+	) {
+		return None;
+	}
+
+	let pixel_size = format.pixel_size();
+	let width = texture.texture_descriptor.size.width as usize;
+	let height = texture.texture_descriptor.size.height as usize;
+	let is_visible = |x: usize, y: usize| texture.data[(y * width + x) * pixel_size + 3] != 0;
+	let (mut min_x, mut min_y, mut max_x, mut max_y) = (width, height, 0, 0);
+	for y in 0..height {
+		for x in 0..width {
+			if is_visible(x, y) {
+				min_x = min_x.min(x);
+				min_y = min_y.min(y);
+				max_x = max_x.max(x + 1);
+				max_y = max_y.max(y + 1);
+			}
+		}
+	}
+	if min_x >= max_x || (max_x - min_x, max_y - min_y) == (width, height) {
+		return None;
+	}
+
+	let (trimmed_width, trimmed_height) = (max_x - min_x, max_y - min_y);
+	let mut trimmed = Image::new_fill(
+		Extent3d {
+			width: trimmed_width as u32,
+			height: trimmed_height as u32,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		&vec![0; pixel_size],
+		format,
+	);
+	trimmed.sampler = texture.sampler.clone();
+	let row_bytes = trimmed_width * pixel_size;
+	for y in 0..trimmed_height {
+		let src = ((min_y + y) * width + min_x) * pixel_size;
+		let dst = y * row_bytes;
+		trimmed.data[dst..dst + row_bytes].copy_from_slice(&texture.data[src..src + row_bytes]);
+	}
+
+	let trim = TileTrim {
+		offset: Vec2::new(min_x as f32, min_y as f32),
+		size: Vec2::new(trimmed_width as f32, trimmed_height as f32),
+		source_size: Vec2::new(width as f32, height as f32),
+	};
+	Some((trimmed, trim))
+}
+
+/// Copy a texture into the top-left corner of a transparent image of the given size
+///
+/// Any part of the texture outside of that size is cut off.
+pub(crate) fn fit_texture(texture: &Image, size: Vec2) -> Image {
+	let format = texture.texture_descriptor.format;
+	let pixel_size = format.pixel_size();
+	let mut fitted = Image::new_fill(
+		Extent3d {
+			width: size.x as u32,
+			height: size.y as u32,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		&vec![0; pixel_size],
+		format,
+	);
+
+	let src_width = texture.texture_descriptor.size.width as usize;
+	let dst_width = size.x as usize;
+	let width = src_width.min(dst_width) * pixel_size;
+	let height = (texture.texture_descriptor.size.height as usize).min(size.y as usize);
+	for y in 0..height {
+		let src = y * src_width * pixel_size;
+		let dst = y * dst_width * pixel_size;
+		fitted.data[dst..dst + width].copy_from_slice(&texture.data[src..src + width]);
+	}
+	fitted
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::{
+		math::Vec2,
+		prelude::Image,
+		render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+	};
+
+	use super::AtlasLayout;
+
+	/// Creates a packed 2x1 grid of 2x2 tiles where every pixel of a tile holds its tile number
+	fn packed_grid() -> Image {
+		#[rustfmt::skip]
+		let pixels: Vec<u8> = vec![
+			1, 1, 2, 2,
+			1, 1, 2, 2,
+		];
+		Image::new(
+			Extent3d {
+				width: 4,
+				height: 2,
+				depth_or_array_layers: 1,
+			},
+			TextureDimension::D2,
+			pixels.into_iter().flat_map(|pixel| [pixel; 4]).collect(),
+			TextureFormat::Rgba8UnormSrgb,
+		)
+	}
+
+	#[test]
+	fn should_pad_tile_rects() {
+		let layout = AtlasLayout::new(Vec2::new(2.0, 2.0), 3, Some(2), 1, 0);
+		assert_eq!(Vec2::new(5.0, 5.0), layout.size());
+		assert_eq!(Vec2::new(0.0, 0.0), layout.tile_rect(0).min);
+		assert_eq!(Vec2::new(3.0, 0.0), layout.tile_rect(1).min);
+		assert_eq!(Vec2::new(0.0, 3.0), layout.tile_rect(2).min);
+		assert_eq!(Vec2::new(2.0, 5.0), layout.tile_rect(2).max);
+	}
+
+	#[test]
+	fn should_copy_tiles_into_padded_layout() {
+		let layout = AtlasLayout::new(Vec2::new(2.0, 2.0), 2, None, 1, 0);
+		let image = layout.apply(packed_grid());
+		assert_eq!(5, image.width());
+		assert_eq!(2, image.height());
+
+		let pixels: Vec<u8> = image.data.chunks_exact(4).map(|pixel| pixel[0]).collect();
+		#[rustfmt::skip]
+		assert_eq!(vec![
+			1, 1, 0, 2, 2,
+			1, 1, 0, 2, 2,
+		], pixels);
+	}
+
+	#[test]
+	fn should_round_up_to_power_of_two() {
+		let layout = AtlasLayout::new(Vec2::new(2.0, 2.0), 3, None, 0, 0).with_power_of_two(true);
+		assert!(!layout.is_packed());
+		assert_eq!(Vec2::new(8.0, 2.0), layout.size());
+		assert_eq!(Vec2::new(4.0, 0.0), layout.tile_rect(2).min);
+
+		let layout = AtlasLayout::new(Vec2::new(2.0, 2.0), 2, None, 0, 0).with_power_of_two(true);
+		assert!(layout.is_packed());
+
+		let image = layout.apply(packed_grid());
+		assert_eq!(4, image.width());
+	}
+
+	#[test]
+	fn should_extrude_tile_edges() {
+		let layout = AtlasLayout::new(Vec2::new(2.0, 2.0), 2, None, 1, 1);
+		assert_eq!(Vec2::new(9.0, 4.0), layout.size());
+		assert_eq!(Vec2::new(16.0, 4.0), layout.with_power_of_two(true).size());
+		assert_eq!(Vec2::new(1.0, 1.0), layout.tile_rect(0).min);
+		assert_eq!(Vec2::new(6.0, 1.0), layout.tile_rect(1).min);
+
+		let image = layout.apply(packed_grid());
+		let pixels: Vec<u8> = image.data.chunks_exact(4).map(|pixel| pixel[0]).collect();
+		#[rustfmt::skip]
+		assert_eq!(vec![
+			1, 1, 1, 1, 0, 2, 2, 2, 2,
+			1, 1, 1, 1, 0, 2, 2, 2, 2,
+			1, 1, 1, 1, 0, 2, 2, 2, 2,
+			1, 1, 1, 1, 0, 2, 2, 2, 2,
+		], pixels);
+	}
+}
@@ -0,0 +1,251 @@
+//! Loading tilesets synchronously, without running a Bevy app
+//!
+//! This reads files directly with `std::fs` and requires the `fs_loader` feature (enabled by
+//! default), which can be disabled for targets without a filesystem such as WASM.
+
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	io,
+	path::{Path, PathBuf},
+};
+
+use bevy::{
+	asset::{io::AssetSourceId, Asset, AssetPath, Assets, Handle},
+	prelude::Image,
+	render::texture::CompressedImageFormats,
+};
+
+use crate::{
+	prelude::{RawTileset, TilesetError},
+	tileset::{
+		asset::{
+			build_tileset,
+			collect_tile_entries,
+			decode_image,
+			parse_tile_def,
+			parse_tileset_def,
+			report_failed_textures,
+			resolve_tile_path,
+			skip_failed_tiles,
+		},
+		load::{load_tile_handles, TextureLoader},
+	},
+};
+
+impl RawTileset {
+	/// Loads and builds a tileset synchronously, reading all of its files from disk
+	///
+	/// This drives the same pipeline as the tileset asset loader to completion in a single call,
+	/// which makes it useful for tests and command-line tools that don't run a Bevy app. Paths
+	/// are resolved the same way as with the `AssetServer`: `path` and the texture paths within
+	/// tile definitions are relative to `asset_root`, while tile definition paths are relative
+	/// to the tileset file. Tile definitions from other asset sources (e.g.
+	/// `"mods://tiles/grass.ron"`) are not supported since there is no `AssetServer` to resolve
+	/// them.
+	///
+	/// # Arguments
+	///
+	/// * `asset_root`: The directory the paths are relative to (e.g. `"assets"`)
+	/// * `path`: The path to the tileset definition file
+	/// * `textures`: The image assets to add the tile textures to
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	/// let mut textures = Assets::<Image>::default();
+	/// let tileset =
+	/// 	RawTileset::load_blocking("assets", "tilesets/my_tileset.ron", &mut textures).unwrap();
+	/// ```
+	pub fn load_blocking<TRoot: AsRef<Path>, TPath: AsRef<Path>>(
+		asset_root: TRoot,
+		path: TPath,
+		textures: &mut Assets<Image>,
+	) -> Result<RawTileset, TilesetError> {
+		let asset_root = asset_root.as_ref();
+		let path = path.as_ref();
+		let bytes = fs::read(asset_root.join(path))?;
+		#[cfg(feature = "gzip")]
+		let bytes = match path.extension().and_then(|ext| ext.to_str()) {
+			Some("gz") => crate::tileset::asset::decompress(path, &bytes)?,
+			_ => bytes,
+		};
+		let mut definition = parse_tileset_def(&bytes)?;
+
+		let base = path.parent().unwrap_or_else(|| Path::new(""));
+		if let Some(manifest) = &definition.manifest {
+			let manifest_path = read_path(&resolve_tile_path(base, manifest))?;
+			let bytes = fs::read(asset_root.join(manifest_path))?;
+			definition.add_manifest_tiles(&bytes)?;
+		}
+		let mut tile_defs = Vec::with_capacity(definition.tiles.len());
+		for tile_path in definition.tiles.values() {
+			let tile_path = read_path(&resolve_tile_path(base, tile_path))?;
+			let bytes = fs::read(asset_root.join(&tile_path))?;
+			let tile_def = parse_tile_def(&tile_path, &bytes)?;
+			tile_defs.push((tile_path, tile_def));
+		}
+		#[allow(unused_mut)]
+		let mut entries = collect_tile_entries(&definition, tile_defs, path)?;
+		#[cfg(feature = "auto-tile")]
+		for entry in entries.iter_mut() {
+			if let Some(ruleset_path) = entry.ruleset_path() {
+				let ruleset_path = read_path(&ruleset_path)?;
+				let bytes = fs::read(asset_root.join(&ruleset_path))?;
+				entry.resolve_ruleset(&ruleset_path, &bytes)?;
+			}
+		}
+
+		let mut loader = BlockingTextureLoader {
+			asset_root,
+			textures,
+			handles: HashMap::new(),
+			failed: Vec::new(),
+		};
+		let handles = load_tile_handles(
+			entries.iter().map(|entry| entry.tile_def.clone()),
+			&mut loader,
+		)?;
+		// Every texture that failed to load shares the default handle
+		let failed_ids = if loader.failed.is_empty() {
+			HashSet::new()
+		} else {
+			HashSet::from([Handle::<Image>::default().id()])
+		};
+		report_failed_textures(loader.failed, definition.strict)?;
+		let (entries, handles) = skip_failed_tiles(entries, handles, &failed_ids);
+
+		build_tileset(definition, entries, handles, textures, None)
+	}
+}
+
+/// Get the path to read a file from, relative to the asset root
+///
+/// Fails for paths from other asset sources since there is no `AssetServer` to resolve them.
+fn read_path(path: &AssetPath) -> Result<PathBuf, TilesetError> {
+	if !matches!(path.source(), AssetSourceId::Default) {
+		return Err(TilesetError::IoError(io::Error::new(
+			io::ErrorKind::Unsupported,
+			format!("cannot read {} without an asset server", path),
+		)));
+	}
+	Ok(path.path().to_path_buf())
+}
+
+/// A [`TextureLoader`] that immediately reads and decodes textures from disk
+struct BlockingTextureLoader<'a> {
+	asset_root: &'a Path,
+	textures: &'a mut Assets<Image>,
+	/// The handles of the textures loaded so far mapped by their path
+	handles: HashMap<PathBuf, Handle<Image>>,
+	/// The paths of the textures that could not be read or decoded along with their errors
+	failed: Vec<(PathBuf, TilesetError)>,
+}
+
+impl<'a> TextureLoader for BlockingTextureLoader<'a> {
+	fn load_texture<'b, T: Asset, P: Into<AssetPath<'b>>>(&mut self, path: P) -> Handle<Image> {
+		let asset_path: AssetPath = path.into();
+		let path = asset_path.path().to_path_buf();
+		if let Some(handle) = self.handles.get(&path) {
+			return handle.clone();
+		}
+
+		let image = fs::read(self.asset_root.join(&path))
+			.map_err(TilesetError::from)
+			.and_then(|bytes| decode_image(&path, &bytes, CompressedImageFormats::all()));
+		let handle = match image {
+			Ok(image) => self.textures.add(image),
+			Err(err) => {
+				self.failed.push((path.clone(), err));
+				Handle::default()
+			},
+		};
+		self.handles.insert(path, handle.clone());
+		handle
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use bevy::prelude::{Assets, Image};
+
+	use crate::{
+		prelude::{RawTileset, TilesetId},
+		tileset::fixtures::TempDir,
+	};
+
+	#[test]
+	fn should_load_blocking() {
+		let dir = TempDir::new("load_blocking");
+		let root = dir.path();
+		fs::create_dir_all(root.join("tilesets")).unwrap();
+		fs::create_dir_all(root.join("tiles")).unwrap();
+		image::RgbaImage::new(4, 4)
+			.save(root.join("tiles/grass.png"))
+			.unwrap();
+		fs::write(
+			root.join("tiles/grass.ron"),
+			r#"(name: "Grass", tile: Standard("tiles/grass.png"))"#,
+		)
+		.unwrap();
+		fs::write(
+			root.join("tilesets/test.ron"),
+			r#"(
+				name: Some("Test"),
+				id: 7,
+				tiles: { 0: "../tiles/grass.ron" },
+				tile_defs: { 1: (name: "Inline", tile: Standard("tiles/grass.png")) },
+			)"#,
+		)
+		.unwrap();
+
+		let mut textures = Assets::<Image>::default();
+		let tileset = RawTileset::load_blocking(root, "tilesets/test.ron", &mut textures).unwrap();
+
+		assert_eq!("Test", tileset.name());
+		assert_eq!(&TilesetId(7), tileset.id());
+		assert_eq!(Some(0), tileset.get_base_tile_index("Grass"));
+		assert_eq!(Some(1), tileset.get_base_tile_index("Inline"));
+	}
+
+	#[test]
+	fn should_skip_unreadable_textures_unless_strict() {
+		let dir = TempDir::new("skip_unreadable");
+		let root = dir.path();
+		fs::create_dir_all(root.join("tiles")).unwrap();
+		image::RgbaImage::new(4, 4)
+			.save(root.join("tiles/grass.png"))
+			.unwrap();
+		fs::write(root.join("tiles/corrupt.png"), b"not an image").unwrap();
+		let tileset = |strict: bool| {
+			format!(
+				r#"(
+					id: 0,
+					strict: {},
+					tile_defs: {{
+						0: (name: "Grass", tile: Standard("tiles/grass.png")),
+						1: (name: "Missing", tile: Standard("tiles/missing.png")),
+						2: (name: "Corrupt", tile: Standard("tiles/corrupt.png")),
+					}},
+				)"#,
+				strict
+			)
+		};
+		fs::write(root.join("lenient.ron"), tileset(false)).unwrap();
+		fs::write(root.join("strict.ron"), tileset(true)).unwrap();
+
+		let mut textures = Assets::<Image>::default();
+		let tileset = RawTileset::load_blocking(root, "lenient.ron", &mut textures).unwrap();
+		assert_eq!(Some(0), tileset.get_base_tile_index("Grass"));
+		assert_eq!(None, tileset.get_base_tile_index("Missing"));
+		assert_eq!(None, tileset.get_base_tile_index("Corrupt"));
+
+		assert!(RawTileset::load_blocking(root, "strict.ron", &mut textures).is_err());
+	}
+}
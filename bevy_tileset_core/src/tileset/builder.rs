@@ -1,8 +1,31 @@
-use crate::{ids::PartialTileId, prelude::*};
-use bevy::prelude::{Handle, Image};
-use bevy_tile_atlas::{TextureStore, TileAtlasBuilder, TileAtlasBuilderError};
+use crate::{
+	ids::PartialTileId,
+	prelude::*,
+	tileset::atlas::{
+		atlas_efficiency,
+		fit_texture,
+		trim_texture,
+		AtlasLayout,
+		LayoutTextureStore,
+	},
+};
+use bevy::{
+	log::debug,
+	prelude::{AssetId, Handle, Image, TextureAtlas, Vec2},
+	reflect::Reflect,
+	render::{
+		render_resource::{Extent3d, TextureDimension},
+		texture::{ImageFilterMode, ImageSampler, ImageSamplerDescriptor, TextureFormatPixelInfo},
+	},
+	utils::Uuid,
+};
+use bevy_tile_atlas::{TextureStore, TileAtlasBuilder};
 use bevy_tileset_tiles::prelude::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
 
 /// A builder for constructing a [`Tileset`]
 #[derive(Default)]
@@ -11,6 +34,20 @@ pub struct TilesetBuilder {
 	tiles: HashMap<TileGroupId, TileData>,
 	/// The builder used to construct the final [`TextureAtlas`]
 	atlas_builder: TileAtlasBuilder,
+	/// The maximum number of columns in the atlas before wrapping
+	max_columns: Option<usize>,
+	/// The maximum number of rows in the atlas
+	max_rows: Option<usize>,
+	/// The transparent space between neighboring tiles in the atlas (in pixels)
+	padding: u32,
+	/// The amount each tile's edges are extruded into its surrounding space (in pixels)
+	extrusion: u32,
+	/// Whether the atlas dimensions are rounded up to the next power of two
+	power_of_two: bool,
+	/// How the atlas texture is sampled (or `None` to use the app's default sampler)
+	filter_mode: Option<ImageFilterMode>,
+	/// The color space the atlas texture is interpreted in
+	color_space: ColorSpace,
 	/// The tile IDs mapped by their name
 	tile_ids: HashMap<String, TileGroupId>,
 	/// The tile names mapped by their ID
@@ -19,6 +56,28 @@ pub struct TilesetBuilder {
 	tile_handles: HashMap<usize, Handle<Image>>,
 	/// The tile IDs mapped by their index in the atlas
 	tile_indices: HashMap<usize, PartialTileId>,
+	/// The explicit durations of animation frames (in seconds) mapped by their index in the atlas
+	frame_durations: HashMap<usize, f32>,
+	/// The definitions of tiles added from a [`TileDef`] mapped by their name
+	tile_defs: HashMap<String, TileDef>,
+	/// Whether tile names are looked up regardless of their casing
+	case_insensitive: bool,
+	/// Whether tiles smaller than the tile size are allowed
+	allow_mixed_sizes: bool,
+	/// The textures of smaller tiles fitted to the tile size mapped by their original ID
+	fitted_textures: HashMap<AssetId<Image>, Image>,
+	/// The actual sizes of smaller tiles mapped by their index in the atlas
+	fitted_sizes: HashMap<usize, Vec2>,
+	/// Whether fully-transparent borders are trimmed from tile textures
+	trim: bool,
+	/// The order the tiles were sorted in before being added (if known)
+	order: Option<TileOrder>,
+	/// The borders trimmed from tile textures mapped by their index in the atlas
+	trims: HashMap<usize, TileTrim>,
+	/// The number of empty cells to insert before the next tile added to the atlas
+	pending_empty_cells: usize,
+	/// The name of the current tile being processed
+	current_name: String,
 	/// The current tile group ID being processed
 	current_group: TileGroupId,
 	/// The current variant index being processed
@@ -29,18 +88,68 @@ pub struct TilesetBuilder {
 	current_auto: Option<usize>,
 }
 
+/// The options a tileset was built with
+///
+/// These are the values passed to the [`TilesetBuilder`] methods of the same name (or set by the
+/// [`TilesetDef`] a tileset was loaded from), and are what [`Tileset::to_def`] exports.
+#[derive(Debug, Default, Clone, Reflect, Deserialize, Serialize)]
+pub struct TilesetOptions {
+	/// The maximum number of columns in the atlas before wrapping
+	pub max_columns: Option<usize>,
+	/// The maximum number of rows in the atlas
+	pub max_rows: Option<usize>,
+	/// The transparent space between neighboring tiles in the atlas (in pixels)
+	pub padding: u32,
+	/// The amount each tile's edges are extruded into its surrounding space (in pixels)
+	pub extrusion: u32,
+	/// Whether the atlas dimensions are rounded up to the next power of two
+	pub power_of_two: bool,
+	/// How the atlas texture is sampled (or `None` if the app's default sampler is used)
+	#[reflect(ignore)]
+	pub filter_mode: Option<ImageFilterMode>,
+	/// The color space the atlas texture is interpreted in
+	pub color_space: ColorSpace,
+	/// Whether tiles smaller than the tile size are allowed
+	pub allow_mixed_sizes: bool,
+	/// Whether fully-transparent borders are trimmed from tile textures
+	pub trim: bool,
+	/// The order the tiles were added in
+	///
+	/// This is only known for tilesets loaded from a [`TilesetDef`], since tiles added directly
+	/// through a [`TilesetBuilder`] are added in whatever order they're given.
+	pub order: Option<TileOrder>,
+}
+
 impl TilesetBuilder {
 	pub fn new(max_columns: Option<usize>) -> Self {
 		let mut atlas_builder = TileAtlasBuilder::default();
 		atlas_builder.max_columns(max_columns);
 		Self {
 			atlas_builder,
+			max_columns,
+			max_rows: None,
+			padding: 0,
+			extrusion: 0,
+			power_of_two: false,
+			filter_mode: None,
+			color_space: ColorSpace::Srgb,
 			tile_ids: Default::default(),
 			current_group: Default::default(),
 			tile_indices: Default::default(),
 			tile_names: Default::default(),
 			tiles: Default::default(),
 			tile_handles: Default::default(),
+			frame_durations: Default::default(),
+			tile_defs: Default::default(),
+			case_insensitive: false,
+			allow_mixed_sizes: false,
+			fitted_textures: Default::default(),
+			fitted_sizes: Default::default(),
+			trim: false,
+			order: None,
+			trims: Default::default(),
+			pending_empty_cells: 0,
+			current_name: Default::default(),
 			#[cfg(feature = "variants")]
 			current_variant: None,
 			#[cfg(feature = "auto-tile")]
@@ -48,25 +157,272 @@ impl TilesetBuilder {
 		}
 	}
 
+	/// Sets the maximum number of rows allowed in the atlas
+	///
+	/// This is only enforced when a maximum number of columns is also set, since the atlas
+	/// is otherwise laid out as a single row. Adding a texture beyond this limit will result
+	/// in a [`TilesetError::AtlasFull`] error.
+	///
+	/// # Arguments
+	///
+	/// * `max_rows`: The maximum number of rows (or `None` for no limit)
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn max_rows(&mut self, max_rows: Option<usize>) -> &mut Self {
+		self.max_rows = max_rows;
+		self
+	}
+
+	/// Sets the transparent space between neighboring tiles in the atlas
+	///
+	/// This helps prevent tiles from bleeding into each other when sampled at
+	/// non-integer scales. The atlas rects still point to the tiles themselves,
+	/// excluding the padding.
+	///
+	/// # Arguments
+	///
+	/// * `padding`: The padding (in pixels)
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn padding(&mut self, padding: u32) -> &mut Self {
+		self.padding = padding;
+		self
+	}
+
+	/// Sets the amount each tile's edges are extruded in the atlas
+	///
+	/// Extrusion duplicates a tile's border pixels outwards, surrounding it with copies of its
+	/// own edges. This prevents seams caused by linear filtering sampling past the edge of a tile,
+	/// and is best combined with [`padding`](Self::padding). The atlas rects still point to the
+	/// tiles themselves, excluding the extruded pixels.
+	///
+	/// # Arguments
+	///
+	/// * `extrusion`: The extrusion (in pixels)
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn extrusion(&mut self, extrusion: u32) -> &mut Self {
+		self.extrusion = extrusion;
+		self
+	}
+
+	/// Sets whether the atlas dimensions are rounded up to the next power of two
+	///
+	/// The extra space is left transparent and does not affect the atlas rects.
+	///
+	/// # Arguments
+	///
+	/// * `power_of_two`: Whether to round up the atlas dimensions
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn power_of_two(&mut self, power_of_two: bool) -> &mut Self {
+		self.power_of_two = power_of_two;
+		self
+	}
+
+	/// Sets how the atlas texture is sampled
+	///
+	/// By default, the atlas uses the app's default sampler (configured through Bevy's
+	/// `ImagePlugin`). Use `Nearest` to keep pixel art crisp or `Linear` for smooth textures
+	/// regardless of that default.
+	///
+	/// # Arguments
+	///
+	/// * `filter_mode`: The filter used when magnifying and minifying the atlas (or `None` to
+	///   use the app's default sampler)
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn filter_mode(&mut self, filter_mode: Option<ImageFilterMode>) -> &mut Self {
+		self.filter_mode = filter_mode;
+		self
+	}
+
+	/// Sets the color space the atlas texture is interpreted in
+	///
+	/// This defaults to `Srgb`, matching the format Bevy loads sprite textures with. Use `Linear`
+	/// if the tile textures contain linear color data, otherwise they'll render washed out.
+	///
+	/// # Arguments
+	///
+	/// * `color_space`: The color space of the atlas texture
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn color_space(&mut self, color_space: ColorSpace) -> &mut Self {
+		self.color_space = color_space;
+		self
+	}
+
+	/// Sets whether tile names are looked up regardless of their casing
+	///
+	/// Names are still stored (and displayed) with their original casing.
+	///
+	/// # Arguments
+	///
+	/// * `case_insensitive`: Whether to ignore casing when looking up tiles by name
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn case_insensitive(&mut self, case_insensitive: bool) -> &mut Self {
+		self.case_insensitive = case_insensitive;
+		self
+	}
+
+	/// Sets whether tiles of different sizes may be added to the atlas
+	///
+	/// The atlas is a uniform grid using the size of the first tile added. By default, adding a
+	/// tile of any other size results in a [`TilesetError::InconsistentTileSize`] error. When
+	/// mixed sizes are allowed, smaller tiles are placed in the top-left corner of their cell and
+	/// their atlas rect only covers their actual size. Larger tiles are still rejected since they
+	/// can't fit in a cell.
+	///
+	/// # Arguments
+	///
+	/// * `allow_mixed_sizes`: Whether to allow tiles smaller than the tile size
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn allow_mixed_sizes(&mut self, allow_mixed_sizes: bool) -> &mut Self {
+		self.allow_mixed_sizes = allow_mixed_sizes;
+		self
+	}
+
+	/// Sets whether fully-transparent borders are trimmed from tile textures
+	///
+	/// Trimmed tiles are placed in the top-left corner of their cell and their atlas rect only
+	/// covers their visible pixels. The trimmed borders are recorded as a [`TileTrim`] (see
+	/// `Tileset::get_trim`) so the tile can still be positioned relative to its original texture.
+	/// Tiles are still checked against the tile size using their untrimmed size.
+	///
+	/// Note that this only changes the sampled rect and the pivot of a tile, not the size of the
+	/// atlas: every tile still takes up a full cell of the atlas grid, trimmed or not.
+	///
+	/// # Arguments
+	///
+	/// * `trim`: Whether to trim tile textures
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn trim(&mut self, trim: bool) -> &mut Self {
+		self.trim = trim;
+		self
+	}
+
+	/// Records the order the tiles were sorted in before being added
+	///
+	/// This doesn't reorder anything: tiles are always added to the atlas in the order they're
+	/// given. It's only kept in the [`TilesetOptions`] of the built tileset.
+	///
+	/// # Arguments
+	///
+	/// * `order`: The order of the tiles
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub(super) fn order(&mut self, order: TileOrder) -> &mut Self {
+		self.order = Some(order);
+		self
+	}
+
+	/// Gets the options the built tileset should record
+	fn options(&self) -> TilesetOptions {
+		TilesetOptions {
+			max_columns: self.max_columns,
+			max_rows: self.max_rows,
+			padding: self.padding,
+			extrusion: self.extrusion,
+			power_of_two: self.power_of_two,
+			filter_mode: self.filter_mode,
+			color_space: self.color_space,
+			allow_mixed_sizes: self.allow_mixed_sizes,
+			trim: self.trim,
+			order: self.order,
+		}
+	}
+
+	/// Gets the index in the atlas the next tile will be added at
+	pub fn next_index(&self) -> usize { self.atlas_builder.len() + self.pending_empty_cells }
+
+	/// Leaves the atlas cells up to the given index empty so that the next tile is added there
+	///
+	/// This is useful for keeping tiles at the same index as a previous build of the tileset
+	/// (e.g. when a tile before them was removed). The empty cells are left transparent and don't
+	/// belong to any tile. Does nothing if the next index is already past the given index.
+	///
+	/// # Arguments
+	///
+	/// * `index`: The index in the atlas the next tile should be added at
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn skip_to_index(&mut self, index: usize) -> &mut Self {
+		self.pending_empty_cells += index.saturating_sub(self.next_index());
+		self
+	}
+
+	/// Build the raw tileset from a set of already-loaded textures
+	///
+	/// Each entry is added as a standard tile, using its position in `entries` as its group ID.
+	/// The result is the same as adding each tile with [`Self::add_tile`] and calling [`Self::build`].
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tileset
+	/// * `id`: The ID of the tileset
+	/// * `entries`: The tile names paired with their textures (in their intended order)
+	/// * `texture_store`: The store of textures
+	///
+	/// returns: Result<RawTileset, TilesetError>
+	///
+	pub fn build_from_textures<TName: Into<String>, TStore: TextureStore>(
+		mut self,
+		name: TName,
+		id: TilesetId,
+		entries: Vec<(String, Handle<Image>)>,
+		texture_store: &mut TStore,
+	) -> Result<RawTileset, TilesetError> {
+		for (group_id, (tile_name, handle)) in entries.into_iter().enumerate() {
+			let tile_handle = TileHandle::new_standard(tile_name, handle);
+			self.add_tile(tile_handle, group_id as TileGroupId, texture_store)?;
+		}
+		self.build(name, id, texture_store)
+	}
+
 	/// Build the raw tileset
 	///
 	/// # Arguments
 	///
 	/// * `texture_store`: The store of textures
 	///
-	/// returns: Result<RawTileset, TextureAtlasBuilderError>
+	/// returns: Result<RawTileset, TilesetError>
 	///
 	pub fn build<TName: Into<String>, TStore: TextureStore>(
-		self,
+		mut self,
 		name: TName,
 		id: TilesetId,
 		texture_store: &mut TStore,
-	) -> Result<RawTileset, TileAtlasBuilderError> {
+	) -> Result<RawTileset, TilesetError> {
 		let tile_size = self.atlas_builder.get_tile_size().unwrap_or_default();
-		let atlas = self.atlas_builder.finish(texture_store)?;
+		let options = self.options();
+		let atlas = self.build_atlas(texture_store)?;
 		let size = atlas.size;
+		let name = name.into();
+		let atlas_efficiency = atlas_efficiency(&atlas.textures, self.tile_handles.keys(), size);
+		debug!(
+			"Packed {} tiles into a {}x{} atlas for tileset {:?} ({:.1}% used)",
+			self.tile_handles.len(),
+			size.x,
+			size.y,
+			name,
+			atlas_efficiency * 100.0
+		);
 		Ok(RawTileset {
-			name: name.into(),
+			name,
 			id,
 			tiles: self.tiles,
 			tile_ids: self.tile_ids,
@@ -77,12 +433,206 @@ impl TilesetBuilder {
 				.collect(),
 			tile_names: self.tile_names,
 			tile_handles: self.tile_handles,
+			frame_durations: self.frame_durations,
+			trims: self.trims,
+			atlas_efficiency,
+			tile_defs: self.tile_defs,
+			case_insensitive: self.case_insensitive,
+			options,
 			tile_size,
 			atlas,
 			size,
+			#[cfg(all(feature = "auto-tile", debug_assertions))]
+			unmatched_warnings: Default::default(),
 		})
 	}
 
+	/// Build multiple raw tilesets that share a single atlas
+	///
+	/// All added tiles are packed into one atlas, which is then split into a tileset for each
+	/// of the given groups. Each tileset only contains (and can only look up) the tiles in its
+	/// group, but their atlases are identical and refer to the same texture, so tiles from all of
+	/// them can be rendered without switching textures. Use [`RawTileset::into_shared_assets`] to
+	/// have the finalized tilesets share a single `Handle<TextureAtlas>` as well.
+	///
+	/// Tiles that don't belong to any group are still packed into the atlas but aren't part of
+	/// any tileset. Tiles in different tilesets may share a name, but retained tile definitions
+	/// are keyed by name, so only the last definition added for a name is kept.
+	///
+	/// # Arguments
+	///
+	/// * `groups`: The name and ID of each tileset paired with the group IDs of its tiles
+	/// * `texture_store`: The store of textures
+	///
+	/// returns: Result<Vec<RawTileset>, TilesetError>
+	///
+	pub fn build_shared<TName: Into<String>, TStore: TextureStore>(
+		mut self,
+		groups: Vec<(TName, TilesetId, Vec<TileGroupId>)>,
+		texture_store: &mut TStore,
+	) -> Result<Vec<RawTileset>, TilesetError> {
+		let tile_size = self.atlas_builder.get_tile_size().unwrap_or_default();
+		let options = self.options();
+		let atlas = self.build_atlas(texture_store)?;
+		let size = atlas.size;
+		debug!(
+			"Packed {} tiles into a shared {}x{} atlas for {} tilesets",
+			self.tile_handles.len(),
+			size.x,
+			size.y,
+			groups.len()
+		);
+		Ok(groups
+			.into_iter()
+			.map(|(name, id, group_ids)| {
+				let group_ids = group_ids.into_iter().collect::<HashSet<_>>();
+				let tile_indices = self
+					.tile_indices
+					.iter()
+					.filter(|(.., tile_id)| group_ids.contains(&tile_id.group_id))
+					.map(|(index, tile_id)| (*index, tile_id.extend(id)))
+					.collect::<HashMap<_, _>>();
+				let tile_names =
+					filter_map(&self.tile_names, |group_id| group_ids.contains(group_id));
+				let tile_ids = tile_names
+					.iter()
+					.map(|(group_id, name)| (name.clone(), *group_id))
+					.collect::<HashMap<_, _>>();
+				let in_group = |index: &usize| tile_indices.contains_key(index);
+				RawTileset {
+					name: name.into(),
+					id,
+					tiles: filter_map(&self.tiles, |group_id| group_ids.contains(group_id)),
+					tile_handles: filter_map(&self.tile_handles, in_group),
+					frame_durations: filter_map(&self.frame_durations, in_group),
+					trims: filter_map(&self.trims, in_group),
+					tile_defs: filter_map(&self.tile_defs, |name| tile_ids.contains_key(name)),
+					case_insensitive: self.case_insensitive,
+					options: options.clone(),
+					tile_size,
+					atlas_efficiency: atlas_efficiency(&atlas.textures, tile_indices.keys(), size),
+					atlas: atlas.clone(),
+					size,
+					#[cfg(all(feature = "auto-tile", debug_assertions))]
+					unmatched_warnings: Default::default(),
+					tile_ids,
+					tile_indices,
+					tile_names,
+				}
+			})
+			.collect())
+	}
+
+	/// Pack the added tiles into a single texture atlas
+	///
+	/// # Arguments
+	///
+	/// * `texture_store`: The store of textures
+	///
+	/// returns: Result<TextureAtlas, TilesetError>
+	///
+	fn build_atlas<TStore: TextureStore>(
+		&mut self,
+		texture_store: &mut TStore,
+	) -> Result<TextureAtlas, TilesetError> {
+		let layout = AtlasLayout::new(
+			self.atlas_builder.get_tile_size().unwrap_or_default(),
+			self.atlas_builder.len(),
+			self.max_columns,
+			self.padding,
+			self.extrusion,
+		)
+		.with_power_of_two(self.power_of_two);
+		let mut store = LayoutTextureStore {
+			store: texture_store,
+			layout,
+			fitted: &self.fitted_textures,
+			sampler: match self.filter_mode {
+				Some(filter_mode) => ImageSampler::Descriptor(ImageSamplerDescriptor {
+					mag_filter: filter_mode,
+					min_filter: filter_mode,
+					mipmap_filter: filter_mode,
+					..Default::default()
+				}),
+				None => ImageSampler::Default,
+			},
+			color_space: self.color_space,
+		};
+		let mut atlas = std::mem::take(&mut self.atlas_builder).finish(&mut store)?;
+		if !layout.is_packed() {
+			atlas = layout.texture_atlas(atlas.texture);
+		}
+		for (index, size) in &self.fitted_sizes {
+			if let Some(rect) = atlas.textures.get_mut(*index) {
+				rect.max = rect.min + *size;
+			}
+		}
+		Ok(atlas)
+	}
+
+	/// Retain the definition a tile was created from
+	///
+	/// The definition can then be retrieved from the built tileset using its
+	/// `get_tile_def` method. Definitions are keyed by their name, so retaining a
+	/// definition replaces any previous one with the same name.
+	///
+	/// # Arguments
+	///
+	/// * `tile_def`: The tile definition
+	///
+	/// returns: &mut TilesetBuilder
+	///
+	pub fn retain_tile_def(&mut self, tile_def: TileDef) -> &mut Self {
+		self.tile_defs.insert(tile_def.name.clone(), tile_def);
+		self
+	}
+
+	/// Add a tile to the tileset being built from its definition
+	///
+	/// The definition's texture paths are resolved using the given loader, which allows
+	/// tilesets to be assembled entirely in code (e.g. with a `HashMap` of paths to
+	/// already-loaded handles). The definition is retained (see [`Self::retain_tile_def`]).
+	///
+	/// # Arguments
+	///
+	/// * `tile_def`: The definition of the tile to add
+	/// * `group_id`: The group ID of the tile (this should be unique across tiles)
+	/// * `texture_loader`: The loader used to resolve the definition's textures
+	/// * `texture_store`: The store of textures
+	///
+	/// returns: Result<Option<TileData>, TilesetError>
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use std::collections::HashMap;
+	/// # use bevy::prelude::*;
+	/// # use bevy_tileset_core::prelude::*;
+	/// # use bevy_tileset_core::tiles::*;
+	///
+	/// fn tileset_creator(textures: Res<Assets<Image>>, my_texture: Handle<Image>) {
+	/// 	let mut builder = TilesetBuilder::default();
+	/// 	let mut loader = HashMap::from([(String::from("my_tile.png"), my_texture)]);
+	/// 	let tile_def = TileDef::new_standard("My Tile", "my_tile.png");
+	/// 	builder.add_tile_def(tile_def, 123, &mut loader, &textures);
+	/// 	// ...
+	/// }
+	/// ```
+	pub fn add_tile_def<TLoader: TextureLoader, TStore: TextureStore>(
+		&mut self,
+		tile_def: TileDef,
+		group_id: TileGroupId,
+		texture_loader: &mut TLoader,
+		texture_store: &TStore,
+	) -> Result<Option<TileData>, TilesetError> {
+		let tile_handle = load_tile_handles([tile_def.clone()], texture_loader)?
+			.pop()
+			.expect("one handle should be loaded per definition");
+		let tile = self.add_tile(tile_handle, group_id, texture_store)?;
+		self.retain_tile_def(tile_def);
+		Ok(tile)
+	}
+
 	/// Add a tile to the tileset being built
 	///
 	/// # Arguments
@@ -118,13 +668,23 @@ impl TilesetBuilder {
 		}
 
 		let name = tile_handle.name.clone();
+		let transform = match &tile_handle.tile {
+			TileHandleType::Reference(reference) => reference.transform(),
+			_ => TileTransform::default(),
+		};
 
 		self.current_group = group_id;
+		self.current_name = name.clone();
 
 		let tile = TileData::new(
 			tile_handle.name,
 			self.get_tile_type(tile_handle.tile, texture_store)?,
-		);
+		)
+		.with_metadata(tile_handle.metadata)
+		.with_tags(tile_handle.tags)
+		.with_group(tile_handle.group)
+		.with_collision(tile_handle.collision)
+		.with_transform(transform);
 
 		self.tile_ids.insert(name.clone(), group_id);
 		self.tile_names.insert(group_id, name);
@@ -149,6 +709,13 @@ impl TilesetBuilder {
 			},
 			#[cfg(feature = "auto-tile")]
 			TileHandleType::Auto(autos) => TileType::Auto(self.create_autos(autos, texture_store)?),
+			TileHandleType::Reference(reference) => self
+				.tile_ids
+				.get(&reference.tile)
+				.and_then(|id| self.tiles.get(id))
+				.ok_or_else(|| TilesetError::TileNotFound(reference.tile.clone()))?
+				.tile()
+				.clone(),
 		})
 	}
 
@@ -165,7 +732,9 @@ impl TilesetBuilder {
 				let auto = AutoTileData::new(
 					auto.rule,
 					self.create_variants(auto.variants, texture_store)?,
-				);
+				)
+				.with_fallback(auto.fallback)
+				.with_corners(auto.corners);
 				self.current_auto = Some(1 + self.current_auto.unwrap_or(0));
 				Ok(auto)
 			})
@@ -211,13 +780,15 @@ impl TilesetBuilder {
 		texture_store: &TStore,
 	) -> Result<AnimatedTileData, TilesetError> {
 		let (mut start, mut end) = (-1, -1);
-		for frame in &anim.frames {
+		for (frame_index, frame) in anim.frames.iter().enumerate() {
 			let index = self.insert_handle(frame, texture_store)?;
+			if let Some(duration) = anim.durations.get(frame_index).copied().flatten() {
+				self.frame_durations.insert(index, duration);
+			}
 			if start == -1 {
 				start = index as i32;
-			} else {
-				end = index as i32;
 			}
+			end = index as i32;
 		}
 
 		if start < 0 || end < 0 {
@@ -227,11 +798,11 @@ impl TilesetBuilder {
 			});
 		}
 
-		Ok(AnimatedTileData::new(
-			anim.speed,
-			start as usize,
-			end as usize,
-		))
+		Ok(
+			AnimatedTileData::new(anim.speed, start as usize, end as usize)
+				.with_mode(anim.mode)
+				.with_random_phase(anim.random_phase),
+		)
 	}
 
 	fn insert_handle<TStore: TextureStore>(
@@ -251,10 +822,56 @@ impl TilesetBuilder {
 		handle: &Handle<Image>,
 		texture: &Image,
 	) -> Result<usize, TilesetError> {
-		let index = self
-			.atlas_builder
-			.add_texture(handle.clone_weak(), texture)
-			.map_err(|err| TilesetError::AtlasError(err))?;
+		if let (Some(columns), Some(rows)) = (self.max_columns, self.max_rows) {
+			let capacity = columns * rows;
+			if self.next_index() >= capacity {
+				return Err(TilesetError::AtlasFull(capacity));
+			}
+		}
+
+		let found = Vec2::new(
+			texture.texture_descriptor.size.width as f32,
+			texture.texture_descriptor.size.height as f32,
+		);
+		let expected = self.atlas_builder.get_tile_size().unwrap_or(found);
+		if found != expected && !(self.allow_mixed_sizes && found.cmple(expected).all()) {
+			return Err(TilesetError::InconsistentTileSize {
+				name: self.current_name.clone(),
+				expected,
+				found,
+			});
+		}
+
+		self.add_empty_cells(texture, expected)?;
+
+		let trimmed = if self.trim {
+			trim_texture(texture)
+		} else {
+			None
+		};
+		let index = match &trimmed {
+			None if found == expected => self
+				.atlas_builder
+				.add_texture(handle.clone_weak(), texture)
+				.map_err(|err| TilesetError::AtlasError(err))?,
+			trimmed => {
+				let (texture, size) = match trimmed {
+					Some((texture, trim)) => (texture, trim.size),
+					None => (texture, found),
+				};
+				let fitted = fit_texture(texture, expected);
+				let index = self
+					.atlas_builder
+					.add_texture(handle.clone_weak(), &fitted)
+					.map_err(|err| TilesetError::AtlasError(err))?;
+				self.fitted_textures.insert(handle.id(), fitted);
+				self.fitted_sizes.insert(index, size);
+				index
+			},
+		};
+		if let Some((.., trim)) = trimmed {
+			self.trims.insert(index, trim);
+		}
 
 		let id = PartialTileId {
 			group_id: self.current_group,
@@ -268,4 +885,389 @@ impl TilesetBuilder {
 
 		Ok(index)
 	}
+
+	/// Add any pending empty cells to the atlas
+	///
+	/// # Arguments
+	///
+	/// * `texture`: The texture of the tile being added (used for its format)
+	/// * `size`: The size of the empty cells
+	///
+	/// returns: Result<(), TilesetError>
+	///
+	fn add_empty_cells(&mut self, texture: &Image, size: Vec2) -> Result<(), TilesetError> {
+		let format = texture.texture_descriptor.format;
+		for _ in 0..std::mem::take(&mut self.pending_empty_cells) {
+			let handle = Handle::<Image>::Weak(AssetId::Uuid {
+				uuid: Uuid::new_v4(),
+			});
+			let empty = Image::new_fill(
+				Extent3d {
+					width: size.x as u32,
+					height: size.y as u32,
+					depth_or_array_layers: 1,
+				},
+				TextureDimension::D2,
+				&vec![0; format.pixel_size()],
+				format,
+			);
+			self.atlas_builder
+				.add_texture(handle.clone(), &empty)
+				.map_err(|err| TilesetError::AtlasError(err))?;
+			self.fitted_textures.insert(handle.id(), empty);
+		}
+		Ok(())
+	}
+}
+
+/// Clone the entries of a map whose keys match the given predicate
+fn filter_map<K: Clone + Eq + Hash, V: Clone>(
+	map: &HashMap<K, V>,
+	predicate: impl Fn(&K) -> bool,
+) -> HashMap<K, V> {
+	map.iter()
+		.filter(|(key, ..)| predicate(key))
+		.map(|(key, value)| (key.clone(), value.clone()))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use bevy::{
+		prelude::{Assets, Image, Vec2},
+		render::{
+			render_resource::TextureFormat,
+			texture::{ImageFilterMode, ImageSampler},
+		},
+		sprite::Anchor,
+	};
+	use bevy_tileset_tiles::prelude::{TileDef, TileHandle};
+
+	use crate::{
+		prelude::{ColorSpace, RawTileset, TileGroupId, TilesetBuilder, TilesetError, TilesetId},
+		tileset::fixtures::{build_solid, solid_image},
+	};
+
+	#[test]
+	fn should_build_from_tile_defs() {
+		let mut textures = Assets::<Image>::default();
+		let mut loader = HashMap::new();
+		for path in ["a.png", "b.png"] {
+			loader.insert(path.to_string(), textures.add(solid_image(2, 2)));
+		}
+
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile_def(
+				TileDef::new_standard("A", "a.png"),
+				0,
+				&mut loader,
+				&textures,
+			)
+			.unwrap();
+		builder
+			.add_tile_def(
+				TileDef::new_standard("B", "b.png"),
+				1,
+				&mut loader,
+				&textures,
+			)
+			.unwrap();
+		let tileset = builder.build("Test", TilesetId(0), &mut textures).unwrap();
+
+		assert_eq!(Some(0), tileset.get_base_tile_index("A"));
+		assert_eq!(Some(1), tileset.get_base_tile_index("B"));
+		assert!(tileset.get_tile_def("A").is_some());
+	}
+
+	#[test]
+	fn should_build_from_textures() {
+		let mut textures = Assets::<Image>::default();
+		let tileset = build_solid(TilesetBuilder::default(), &["A", "B", "C"], &mut textures);
+
+		assert_eq!(Some(2), tileset.get_base_tile_index("C"));
+		assert_eq!(Some(&1), tileset.get_tile_group_id("B"));
+	}
+
+	#[test]
+	fn should_set_filter_mode() {
+		let mut textures = Assets::<Image>::default();
+
+		// The app's default sampler is kept unless a filter mode is set
+		let tileset = build_solid(TilesetBuilder::default(), &["Grass"], &mut textures);
+		let texture = textures.get(&tileset.atlas().texture).unwrap();
+		assert!(matches!(&texture.sampler, ImageSampler::Default));
+
+		let mut builder = TilesetBuilder::default();
+		builder.filter_mode(Some(ImageFilterMode::Linear));
+		let tileset = build_solid(builder, &["Grass"], &mut textures);
+
+		let texture = textures.get(&tileset.atlas().texture).unwrap();
+		assert!(matches!(
+			&texture.sampler,
+			ImageSampler::Descriptor(descriptor)
+				if matches!(descriptor.mag_filter, ImageFilterMode::Linear)
+		));
+	}
+
+	#[test]
+	fn should_set_color_space() {
+		let mut textures = Assets::<Image>::default();
+
+		let tileset = build_solid(TilesetBuilder::default(), &["Grass"], &mut textures);
+		let texture = textures.get(&tileset.atlas().texture).unwrap();
+		assert_eq!(
+			TextureFormat::Rgba8UnormSrgb,
+			texture.texture_descriptor.format
+		);
+
+		let mut builder = TilesetBuilder::default();
+		builder.color_space(ColorSpace::Linear);
+		let tileset = build_solid(builder, &["Grass"], &mut textures);
+		let texture = textures.get(&tileset.atlas().texture).unwrap();
+		assert_eq!(TextureFormat::Rgba8Unorm, texture.texture_descriptor.format);
+		assert_eq!(&[255, 255, 255, 255], &texture.data[..4]);
+	}
+
+	#[test]
+	fn should_trim_transparent_borders() {
+		let mut textures = Assets::<Image>::default();
+		let mut image = solid_image(4, 4);
+		image.data.fill(0);
+		// Make the pixels at (1, 2) and (2, 2) visible
+		image.data[(2 * 4 + 1) * 4..(2 * 4 + 3) * 4].fill(255);
+		let entries = vec![("Grass".to_string(), textures.add(image))];
+
+		let mut builder = TilesetBuilder::default();
+		builder.trim(true);
+		let tileset = builder
+			.build_from_textures("Test", TilesetId(0), entries, &mut textures)
+			.unwrap();
+
+		let trim = tileset.get_trim(&0).unwrap();
+		assert_eq!(Vec2::new(1.0, 2.0), trim.offset);
+		assert_eq!(Vec2::new(2.0, 1.0), trim.size);
+		assert_eq!(Vec2::new(4.0, 4.0), tileset.tile_size());
+		let rect = tileset.atlas().textures[0];
+		assert_eq!(Vec2::new(2.0, 1.0), rect.size());
+
+		let texture = textures.get(&tileset.atlas().texture).unwrap();
+		assert_eq!(&[255; 8], &texture.data[..8]);
+		assert!(matches!(
+			trim.anchor(),
+			Anchor::Custom(anchor) if anchor == Vec2::new(0.0, 0.5)
+		));
+	}
+
+	#[test]
+	fn should_create_sprites_with_flips_and_anchor() {
+		let mut textures = Assets::<Image>::default();
+		let mut image = solid_image(4, 4);
+		// Only keep the top row visible
+		image.data[4 * 4..].fill(0);
+		let mut loader = HashMap::from([("grass.png".to_string(), textures.add(image))]);
+		let flipped = ron::from_str::<TileDef>(
+			r#"(name: "Flipped", tile: Reference((tile: "Grass", flip_x: true)))"#,
+		)
+		.unwrap();
+
+		let mut builder = TilesetBuilder::default();
+		builder.trim(true);
+		builder
+			.add_tile_def(
+				TileDef::new_standard("Grass", "grass.png"),
+				0,
+				&mut loader,
+				&textures,
+			)
+			.unwrap();
+		builder
+			.add_tile_def(flipped, 1, &mut loader, &textures)
+			.unwrap();
+		let tileset = builder.build("Test", TilesetId(0), &mut textures).unwrap();
+
+		let grass = tileset.tile_sprite("Grass").unwrap();
+		let flipped = tileset.tile_sprite("Flipped").unwrap();
+		assert!(!grass.flip_x);
+		assert!(flipped.flip_x);
+		assert_eq!(grass.index, flipped.index);
+		assert!(matches!(
+			flipped.anchor,
+			Anchor::Custom(anchor) if anchor == Vec2::new(0.0, -1.5)
+		));
+	}
+
+	#[test]
+	fn should_compute_atlas_efficiency() {
+		let mut textures = Assets::<Image>::default();
+
+		// A 2x2 grid with an empty cell in the last row
+		let tileset = build_solid(
+			TilesetBuilder::new(Some(2)),
+			&["A", "B", "C"],
+			&mut textures,
+		);
+
+		assert_eq!(0.75, tileset.atlas_efficiency());
+	}
+
+	#[test]
+	fn should_skip_to_index() {
+		let mut textures = Assets::<Image>::default();
+		let mut add_image = || textures.add(solid_image(2, 2));
+		let (a, b) = (add_image(), add_image());
+
+		let mut builder = TilesetBuilder::default();
+		builder.skip_to_index(1);
+		builder
+			.add_tile(TileHandle::new_standard("A", a), 0, &textures)
+			.unwrap();
+		builder.skip_to_index(3);
+		assert_eq!(3, builder.next_index());
+		builder
+			.add_tile(TileHandle::new_standard("B", b), 1, &textures)
+			.unwrap();
+		let tileset = builder.build("Test", TilesetId(0), &mut textures).unwrap();
+
+		assert_eq!(Some(1), tileset.get_base_tile_index("A"));
+		assert_eq!(Some(3), tileset.get_base_tile_index("B"));
+		assert_eq!(4, tileset.atlas().textures.len());
+		assert_eq!(0.5, tileset.atlas_efficiency());
+	}
+
+	#[test]
+	fn should_build_shared_atlas() {
+		let mut textures = Assets::<Image>::default();
+		let entries = ["Grass", "Dirt", "Wall"]
+			.into_iter()
+			.map(|name| {
+				let image = solid_image(2, 2);
+				TileHandle::new_standard(name, textures.add(image))
+			})
+			.collect::<Vec<_>>();
+
+		let mut builder = TilesetBuilder::default();
+		for (group_id, tile) in entries.into_iter().enumerate() {
+			builder
+				.add_tile(tile, group_id as TileGroupId, &textures)
+				.unwrap();
+		}
+		let tilesets = builder
+			.build_shared(
+				vec![
+					("Terrain", TilesetId(0), vec![0, 1]),
+					("Walls", TilesetId(1), vec![2]),
+				],
+				&mut textures,
+			)
+			.unwrap();
+
+		let (terrain, walls) = (&tilesets[0], &tilesets[1]);
+		assert_eq!(Some(1), terrain.get_base_tile_index("Dirt"));
+		assert_eq!(None, terrain.get_base_tile_index("Wall"));
+		assert_eq!(Some(2), walls.get_base_tile_index("Wall"));
+		assert_eq!(None, walls.get_base_tile_index("Grass"));
+		assert_eq!(TilesetId(1), walls.tile_id("Wall").unwrap().tileset_id);
+		assert_eq!(terrain.atlas().texture, walls.atlas().texture);
+
+		let mut atlases = Assets::default();
+		let tilesets = RawTileset::into_shared_assets(tilesets, &mut atlases);
+		assert_eq!(tilesets[0].atlas(), tilesets[1].atlas());
+		assert_eq!(1, atlases.len());
+	}
+
+	#[test]
+	fn should_build_shared_atlas_with_shared_tile_names() {
+		let mut textures = Assets::<Image>::default();
+		let mut builder = TilesetBuilder::default();
+		for group_id in 0..2 {
+			let tile = TileHandle::new_standard("Grass", textures.add(solid_image(2, 2)));
+			builder.add_tile(tile, group_id, &textures).unwrap();
+		}
+		let tilesets = builder
+			.build_shared(
+				vec![
+					("Summer", TilesetId(0), vec![0]),
+					("Winter", TilesetId(1), vec![1]),
+				],
+				&mut textures,
+			)
+			.unwrap();
+
+		for (group_id, tileset) in tilesets.iter().enumerate() {
+			assert_eq!(Some(group_id), tileset.get_base_tile_index("Grass"));
+			assert_eq!(
+				group_id as TileGroupId,
+				tileset.tile_id("Grass").unwrap().group_id
+			);
+			assert_eq!(1, tileset.tile_ids.len());
+		}
+	}
+
+	#[test]
+	fn should_lookup_case_insensitive() {
+		let mut textures = Assets::<Image>::default();
+
+		let mut builder = TilesetBuilder::default();
+		builder.case_insensitive(true);
+		let tileset = build_solid(builder, &["Grass"], &mut textures);
+
+		assert_eq!(Some(&0), tileset.get_tile_group_id("grass"));
+		assert_eq!(Some(&"Grass".to_string()), tileset.get_tile_name(&0));
+	}
+
+	#[test]
+	fn should_prefer_exact_case_matches() {
+		let mut textures = Assets::<Image>::default();
+		let names = ["Grass", "grass"];
+
+		let tileset = build_solid(TilesetBuilder::default(), &names, &mut textures);
+		assert_eq!(Some(&0), tileset.get_tile_group_id("Grass"));
+		assert_eq!(Some(&1), tileset.get_tile_group_id("grass"));
+		assert_eq!(None, tileset.get_tile_group_id("GRASS"));
+
+		let mut builder = TilesetBuilder::default();
+		builder.case_insensitive(true);
+		let tileset = build_solid(builder, &names, &mut textures);
+		assert_eq!(Some(&0), tileset.get_tile_group_id("Grass"));
+		assert_eq!(Some(&1), tileset.get_tile_group_id("grass"));
+		assert_eq!(Some(1), tileset.get_base_tile_index("grass"));
+		assert!(matches!(tileset.get_tile_group_id("GRASS"), Some(0 | 1)));
+	}
+
+	#[test]
+	fn should_check_tile_sizes() {
+		let mut textures = Assets::<Image>::default();
+		let mut entries = || {
+			[("Large", 4), ("Small", 2)]
+				.into_iter()
+				.map(|(name, size)| {
+					let image = solid_image(size, size);
+					(name.to_string(), textures.add(image))
+				})
+				.collect::<Vec<_>>()
+		};
+		let (strict, mixed) = (entries(), entries());
+
+		let result = TilesetBuilder::default().build_from_textures(
+			"Test",
+			TilesetId(0),
+			strict,
+			&mut textures,
+		);
+		assert!(matches!(
+			result,
+			Err(TilesetError::InconsistentTileSize { name, .. }) if name == "Small"
+		));
+
+		let mut builder = TilesetBuilder::default();
+		builder.allow_mixed_sizes(true);
+		let tileset = builder
+			.build_from_textures("Test", TilesetId(0), mixed, &mut textures)
+			.unwrap();
+		assert_eq!(Vec2::new(4.0, 4.0), tileset.tile_size());
+		assert_eq!(Vec2::new(2.0, 2.0), tileset.atlas().textures[1].size());
+	}
 }
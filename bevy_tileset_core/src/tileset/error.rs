@@ -18,12 +18,69 @@ pub enum TilesetError {
 	ReadAssetBytesError(ReadAssetBytesError),
 	#[error("could not read image: {0:?}")]
 	ImageError(TextureError),
+	#[error("could not find the following tile textures: {0:?}")]
+	MissingTextures(Vec<std::path::PathBuf>),
+	#[error("could not determine the image format of {0:?}")]
+	UnknownImageFormat(std::path::PathBuf),
 	#[error("could not add tile to atlas: {0:?}")]
 	AtlasError(TileAtlasBuilderError),
+	#[error("could not add tile to atlas: atlas is full (capacity of {0} tiles)")]
+	AtlasFull(usize),
+	#[error("tile {name:?} has a size of {found} but the tileset expects {expected}")]
+	InconsistentTileSize {
+		name: String,
+		expected: bevy::math::Vec2,
+		found: bevy::math::Vec2,
+	},
+	#[error("could not export atlas texture: {0}")]
+	AtlasExportError(String),
+	#[error("could not export tileset: {0}")]
+	ExportError(String),
 	#[error("invalid tile data (expected {expected:?}, found {found:?})")]
 	InvalidData { expected: String, found: String },
 	#[error("could not read tile definition file: {0:?}")]
 	InvalidDefinition(ron::error::SpannedError),
+	#[cfg(feature = "json")]
+	#[error("could not read JSON tile definition file {0:?}: {1:?}")]
+	InvalidJsonDefinition(std::path::PathBuf, serde_json::Error),
+	#[cfg(feature = "yaml")]
+	#[error("could not read YAML tile definition file {0:?}: {1:?}")]
+	InvalidYamlDefinition(std::path::PathBuf, serde_yaml::Error),
+	#[cfg(feature = "aseprite")]
+	#[error("could not read Aseprite file: {0}")]
+	InvalidAseprite(String),
+	#[cfg(feature = "ldtk")]
+	#[error("could not read LDtk project: {0}")]
+	InvalidLdtk(String),
+	#[cfg(feature = "tiled")]
+	#[error("could not read Tiled tileset: {0}")]
+	InvalidTsx(String),
+	#[cfg(feature = "auto-tile")]
+	#[error("invalid auto tile rule set {0:?}: {1}")]
+	InvalidRuleset(std::path::PathBuf, String),
+	#[cfg(feature = "auto-tile")]
+	#[error("the auto tile rule set of tile {0:?} was not resolved")]
+	UnresolvedRuleset(String),
+	#[error("could not read tile manifest {0:?}: {1}")]
+	InvalidManifest(std::path::PathBuf, String),
+	#[cfg(feature = "zip")]
+	#[error("could not read tileset archive: {0}")]
+	InvalidArchive(String),
+	#[cfg(feature = "gzip")]
+	#[error("could not decompress {0:?}: {1:?}")]
+	DecompressionError(std::path::PathBuf, std::io::Error),
+	#[error("tile name {name:?} is defined by both {first:?} and {second:?}")]
+	DuplicateTileName {
+		name: String,
+		first: std::path::PathBuf,
+		second: std::path::PathBuf,
+	},
+	#[error("tileset {0:?} could not be found")]
+	TilesetNotFound(String),
+	#[error("tileset {0:?} already exists")]
+	TilesetAlreadyExists(String),
+	#[error("tile {0:?} could not be found")]
+	TileNotFound(String),
 	#[error("tile with group ID {0:?} already exists in the tileset")]
 	TileAlreadyExists(TileGroupId),
 	#[error("could not build tile atlas: {0:?}")]
@@ -0,0 +1,126 @@
+//! Exporting tilesets to the single-file RON tileset format
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use ron::ser::PrettyConfig;
+
+use crate::prelude::{Tileset, TilesetDef, TilesetError};
+
+impl Tileset {
+	/// Creates a [`TilesetDef`] defining every tile of this tileset inline
+	///
+	/// The atlas options (e.g. `max_columns`, `padding`, and `filter_mode`) are taken from the
+	/// [`TilesetOptions`] this tileset was built with. Since only tiles added from a [`TileDef`]
+	/// retain their definition, this fails if any tile was added some other way (e.g. from a
+	/// `TileHandle`).
+	///
+	/// returns: Result<TilesetDef, TilesetError>
+	///
+	/// [`TileDef`]: bevy_tileset_tiles::prelude::TileDef
+	/// [`TilesetOptions`]: crate::prelude::TilesetOptions
+	pub fn to_def(&self) -> Result<TilesetDef, TilesetError> {
+		let mut tile_defs = BTreeMap::new();
+		for (group_id, data) in self.tiles.iter() {
+			let tile_def = self.tile_defs.get(data.name()).ok_or_else(|| {
+				TilesetError::ExportError(format!(
+					"tile {:?} was not added from a tile definition",
+					data.name()
+				))
+			})?;
+			tile_defs.insert(*group_id, tile_def.clone());
+		}
+
+		let options = &self.options;
+		Ok(TilesetDef {
+			name: Some(self.name().to_string()),
+			id: *self.id(),
+			tile_defs,
+			max_columns: options.max_columns,
+			max_rows: options.max_rows,
+			padding: Some(options.padding),
+			extrusion: Some(options.extrusion),
+			power_of_two: Some(options.power_of_two),
+			filter_mode: options.filter_mode,
+			color_space: options.color_space,
+			allow_mixed_sizes: options.allow_mixed_sizes,
+			trim: options.trim,
+			order: options.order.unwrap_or_default(),
+			..Default::default()
+		})
+	}
+
+	/// Exports this tileset as a single-file tileset definition (e.g. `my_tileset.tileset.ron`)
+	///
+	/// Every tile is written inline along with its variants, animation, and other data (see
+	/// [`Tileset::to_def`]), so the exported file can be loaded back as a [`Tileset`]. This makes
+	/// it possible to persist a tileset modified at runtime or to migrate a tileset defined
+	/// across multiple files to a single file.
+	///
+	/// # Arguments
+	///
+	/// * `path`: The path of the file to write
+	///
+	/// returns: Result<(), TilesetError>
+	///
+	pub fn export_ron<P: AsRef<Path>>(&self, path: P) -> Result<(), TilesetError> {
+		let def = self.to_def()?;
+		let ron = ron::ser::to_string_pretty(&def, PrettyConfig::default())
+			.map_err(|err| TilesetError::ExportError(err.to_string()))?;
+		fs::write(path, ron)?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use bevy::prelude::{Assets, Image, TextureAtlas};
+	use bevy_tileset_tiles::prelude::TileDef;
+
+	use crate::{
+		prelude::{ColorSpace, TilesetBuilder, TilesetDef, TilesetId},
+		tileset::fixtures::solid_image,
+	};
+
+	#[test]
+	fn should_export_tile_defs_and_atlas_options() {
+		let mut textures = Assets::<Image>::default();
+		let mut loader = HashMap::new();
+		// More columns than tiles, which can't be told apart from a single row by the atlas alone
+		let mut builder = TilesetBuilder::new(Some(4));
+		builder
+			.max_rows(Some(3))
+			.padding(2)
+			.extrusion(1)
+			.color_space(ColorSpace::Linear)
+			.trim(true);
+		for (group_id, name) in ["A", "B", "C"].into_iter().enumerate() {
+			let image = solid_image(4, 4);
+			let path = format!("{}.png", name);
+			loader.insert(path.clone(), textures.add(image));
+			let tile_def = TileDef::new_standard(name, path);
+			builder
+				.add_tile_def(tile_def, group_id as u32, &mut loader, &textures)
+				.unwrap();
+		}
+		let raw_tileset = builder.build("Test", TilesetId(3), &mut textures).unwrap();
+		let tileset = raw_tileset.into_asset(&mut Assets::<TextureAtlas>::default());
+
+		let def = tileset.to_def().unwrap();
+		let def = ron::from_str::<TilesetDef>(&ron::to_string(&def).unwrap()).unwrap();
+
+		assert_eq!(Some("Test".to_string()), def.name);
+		assert_eq!(TilesetId(3), def.id);
+		assert_eq!(Some(4), def.max_columns);
+		assert_eq!(Some(3), def.max_rows);
+		assert_eq!(Some(2), def.padding);
+		assert_eq!(Some(1), def.extrusion);
+		assert_eq!(Some(false), def.power_of_two);
+		assert_eq!(ColorSpace::Linear, def.color_space);
+		assert!(def.trim);
+		assert!(!def.allow_mixed_sizes);
+		assert!(def.tiles.is_empty());
+		assert_eq!("C", def.tile_defs[&2].name);
+	}
+}
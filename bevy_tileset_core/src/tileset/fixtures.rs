@@ -0,0 +1,157 @@
+//! Shared fixtures for the tileset tests
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+use bevy::{
+	asset::LoadState,
+	prelude::*,
+	render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+	utils::Uuid,
+};
+
+#[cfg(feature = "auto-tile")]
+use bevy_tileset_tiles::prelude::*;
+
+use crate::prelude::{RawTileset, Tileset, TilesetBuilder, TilesetId, TilesetPlugin};
+
+/// The directory containing the fixture files
+pub(crate) const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// A unique temporary directory that is removed once dropped
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+	pub(crate) fn new(name: &str) -> Self {
+		let path = std::env::temp_dir().join(format!("bevy_tileset_{}_{}", name, Uuid::new_v4()));
+		fs::create_dir_all(&path).unwrap();
+		Self(path)
+	}
+
+	pub(crate) fn path(&self) -> &Path { &self.0 }
+}
+
+impl Drop for TempDir {
+	fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+}
+
+/// Creates an sRGB image of the given size filled with opaque white
+pub(crate) fn solid_image(width: u32, height: u32) -> Image {
+	Image::new_fill(
+		Extent3d {
+			width,
+			height,
+			depth_or_array_layers: 1,
+		},
+		TextureDimension::D2,
+		&[255, 255, 255, 255],
+		TextureFormat::Rgba8UnormSrgb,
+	)
+}
+
+/// Builds a tileset named `"Test"` with a 2x2 [`solid_image`] for each of the given tile names
+///
+/// Each name's group ID is its position in `names`.
+pub(crate) fn build_solid(
+	builder: TilesetBuilder,
+	names: &[&str],
+	textures: &mut Assets<Image>,
+) -> RawTileset {
+	let entries = names
+		.iter()
+		.map(|name| (name.to_string(), textures.add(solid_image(2, 2))))
+		.collect();
+	builder
+		.build_from_textures("Test", TilesetId(0), entries, textures)
+		.unwrap()
+}
+
+/// Creates a rule matching exactly the given 4-bit edge value (see [`NeighborMask`])
+#[cfg(feature = "auto-tile")]
+pub(crate) fn edge_rule(edges: u8) -> AutoTileRule {
+	AutoTileRule {
+		north: Some(edges & 1 != 0),
+		east: Some(edges & 2 != 0),
+		south: Some(edges & 4 != 0),
+		west: Some(edges & 8 != 0),
+		..Default::default()
+	}
+}
+
+/// Builds a tileset with a `"Wall"` auto tile made of the given rules, fallback flags, and corners
+///
+/// Each sub-tile has a single variant, so its index is its position in `autos`.
+#[cfg(feature = "auto-tile")]
+pub(crate) fn build_walls(autos: &[(AutoTileRule, bool, Option<u8>)]) -> RawTileset {
+	let mut textures = Assets::<Image>::default();
+	let autos = autos
+		.iter()
+		.map(|(rule, fallback, corners)| AutoTileHandle {
+			rule: *rule,
+			variants: vec![VariantTileHandle {
+				weight: 1.0,
+				tile: SimpleTileHandle::Standard(textures.add(solid_image(2, 2))),
+			}],
+			fallback: *fallback,
+			corners: *corners,
+		})
+		.collect();
+	let mut builder = TilesetBuilder::default();
+	builder
+		.add_tile(TileHandle::new_auto("Wall", autos), 0, &textures)
+		.unwrap();
+	builder.build("Walls", TilesetId(0), &mut textures).unwrap()
+}
+
+/// Creates an app with a [`TilesetPlugin`] that loads assets from the given directory
+pub(crate) fn asset_app<P: AsRef<Path>>(root: P) -> App {
+	let mut app = App::new();
+	app.add_plugins((
+		MinimalPlugins,
+		AssetPlugin {
+			file_path: root.as_ref().to_string_lossy().to_string(),
+			..Default::default()
+		},
+	))
+	.init_asset::<Image>()
+	.init_asset::<TextureAtlas>()
+	.add_plugins(TilesetPlugin::default());
+	app
+}
+
+/// Updates the app until the given asset has either loaded or failed to load
+pub(crate) fn wait_for_load<A: Asset>(app: &mut App, handle: &Handle<A>) -> LoadState {
+	for _ in 0..1000 {
+		app.update();
+		let state = app.world.resource::<AssetServer>().load_state(handle);
+		if matches!(state, LoadState::Loaded | LoadState::Failed) {
+			// Let the tileset systems handle the resulting asset events
+			app.update();
+			return state;
+		}
+		std::thread::sleep(Duration::from_millis(5));
+	}
+	panic!("timed out loading {:?}", handle.path());
+}
+
+/// Builds a tileset with a single `"Grass"` tile and adds it to the app's assets
+///
+/// The app is updated until the tileset is registered.
+pub(crate) fn add_tileset(app: &mut App, name: &str, id: TilesetId) -> Handle<Tileset> {
+	let world = &mut app.world;
+	let raw_tileset = world.resource_scope(|_, mut textures: Mut<Assets<Image>>| {
+		let entries = vec![("Grass".to_string(), textures.add(solid_image(2, 2)))];
+		TilesetBuilder::default()
+			.build_from_textures(name, id, entries, &mut *textures)
+			.unwrap()
+	});
+	let tileset = raw_tileset.into_asset(&mut world.resource_mut::<Assets<TextureAtlas>>());
+	let handle = world.resource_mut::<Assets<Tileset>>().add(tileset);
+	// Asset events are sent at the end of a frame and handled in the next one
+	app.update();
+	app.update();
+	handle
+}
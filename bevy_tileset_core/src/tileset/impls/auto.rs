@@ -1,5 +1,14 @@
 //! Implementation details for Auto Tiles
 
+#[cfg(debug_assertions)]
+use std::{
+	collections::HashSet,
+	sync::{Mutex, PoisonError},
+};
+
+#[cfg(debug_assertions)]
+use bevy::log::warn;
+
 use crate::prelude::{PartialTileId, RawTileset, TileIndex, Tileset};
 use bevy_tileset_tiles::prelude::*;
 
@@ -89,6 +98,94 @@ macro_rules! impl_tileset {
 				}
 			}
 
+			/// Resolves the base index into the `TextureAtlas` for an auto tile, given which of
+			/// its neighbors match it
+			///
+			/// The auto tile whose rule matches the given neighbors is chosen (see [`NeighborMask`]
+			/// for the bit layout). Auto tiles only defining edge rules (i.e. leaving the corners
+			/// as `None`) can be resolved using the 16-value edge scheme.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the auto tile
+			/// * `neighbors`: The neighbors matching the tile
+			///
+			/// returns: Option<usize>
+			///
+			/// # Examples
+			///
+			/// ```
+			/// # use bevy_tileset_core::prelude::*;
+			/// # use bevy_tileset_tiles::prelude::NeighborMask;
+			/// fn get_index(tileset: &Tileset) {
+			/// 	let neighbors = NeighborMask::NORTH.with(NeighborMask::SOUTH, true);
+			/// 	let index = tileset.resolve_auto_tile("My Auto Tile", neighbors);
+			/// }
+			/// ```
+			pub fn resolve_auto_tile(&self, name: &str, neighbors: NeighborMask) -> Option<usize> {
+				#[cfg(debug_assertions)]
+				if let TileType::Auto(autos) = self.get_tile_data(name)?.tile() {
+					let rule = neighbors.into();
+					if !autos.iter().any(|auto| auto.matches(&rule)) {
+						warn_unmatched(&self.unmatched_warnings, name, neighbors);
+					}
+				}
+
+				let index = self.get_auto_index(name, neighbors.into())?;
+				Some(*index.base_index())
+			}
+
+			/// Resolves the base index into the `TextureAtlas` for a corner-matched auto tile, given
+			/// which corners of a marching squares cell are inside the isoline
+			///
+			/// The corners are given in the order `[top_left, top_right, bottom_right, bottom_left]`.
+			/// Using the canonical numbering, the marching squares case is
+			/// `8 * top_left + 4 * top_right + 2 * bottom_right + bottom_left` (from case 0, with
+			/// no corners inside, to case 15, with every corner inside). Each case maps to the
+			/// sub-tile whose `corners` match:
+			///
+			/// | Corner       | Case Bit | `corners` Bit |
+			/// |--------------|----------|---------------|
+			/// | Top-Left     | 8        | 8 (NW)        |
+			/// | Top-Right    | 4        | 1 (NE)        |
+			/// | Bottom-Right | 2        | 2 (SE)        |
+			/// | Bottom-Left  | 1        | 4 (SW)        |
+			///
+			/// Like [`resolve_auto_tile`](Self::resolve_auto_tile), the fallback sub-tile is used for
+			/// cases without a matching sub-tile.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the auto tile
+			/// * `corners`: Whether each corner is inside the isoline
+			///
+			/// returns: Option<usize>
+			///
+			/// # Examples
+			///
+			/// ```
+			/// # use bevy_tileset_core::prelude::*;
+			/// fn get_index(tileset: &Tileset) {
+			/// 	// Case 6: only the right side is inside
+			/// 	let index = tileset.marching_squares("My Terrain", [false, true, true, false]);
+			/// }
+			/// ```
+			pub fn marching_squares(&self, name: &str, corners: [bool; 4]) -> Option<usize> {
+				let [top_left, top_right, bottom_right, bottom_left] = corners;
+				let neighbors = [
+					(top_left, NeighborMask::NORTH_WEST),
+					(top_right, NeighborMask::NORTH_EAST),
+					(bottom_right, NeighborMask::SOUTH_EAST),
+					(bottom_left, NeighborMask::SOUTH_WEST),
+				]
+				.into_iter()
+				// Every edge is set so that each corner is filled exactly when it's inside
+				.fold(NeighborMask::EDGES, |mask, (inside, corner)| {
+					mask.with(corner, inside)
+				});
+				self.resolve_auto_tile(name, neighbors)
+			}
+
 			/// Checks if the given index is a variant for a given auto tile rule
 			///
 			/// This is an important method because it allows the auto tile system to skip tiles that
@@ -110,7 +207,7 @@ macro_rules! impl_tileset {
 				if let Some(data) = self.get_tile_data(name) {
 					match data.tile() {
 						TileType::Auto(autos) => {
-							if let Some(auto) = autos.iter().find(|a| a.rule().is_subset_of(rule)) {
+							if let Some(auto) = autos.iter().find(|a| a.matches(rule)) {
 								// Check if _any_ variant matches the given index
 								auto.variants()
 									.iter()
@@ -137,10 +234,13 @@ macro_rules! impl_tileset {
 				} else {
 					match auto_tiles
 						.iter()
-						.find(|&auto| auto.rule().is_subset_of(&rule))
+						.find(|&auto| auto.matches(&rule))
 					{
 						Some(t) => t,
-						None => auto_tiles.last()?,
+						None => auto_tiles
+							.iter()
+							.find(|&auto| auto.is_fallback())
+							.or_else(|| auto_tiles.last())?,
 					}
 				};
 
@@ -156,5 +256,172 @@ macro_rules! impl_tileset {
 	};
 }
 
+/// Logs a warning the first time the given neighbors don't match any rule of an auto tile
+///
+/// This only happens in debug builds to help spot missing rules. Each tileset keeps track of
+/// the warnings it has already logged.
+#[cfg(debug_assertions)]
+fn warn_unmatched(
+	warned: &Mutex<HashSet<(String, NeighborMask)>>,
+	name: &str,
+	neighbors: NeighborMask,
+) {
+	let mut warned = warned.lock().unwrap_or_else(PoisonError::into_inner);
+	if warned.insert((name.to_string(), neighbors)) {
+		warn!(
+			"auto tile {:?} has no rule matching the neighbors {:#010b}, using its fallback",
+			name, neighbors.0
+		);
+	}
+}
+
 impl_tileset!(Tileset);
 impl_tileset!(RawTileset);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tileset::fixtures::{build_walls, edge_rule};
+
+	#[test]
+	fn should_use_fallback_when_no_rule_matches() {
+		let (north, south) = (edge_rule(0b0001), edge_rule(0b0100));
+
+		let tileset = build_walls(&[
+			(north, false, None),
+			(AutoTileRule::default(), true, None),
+			(south, false, None),
+		]);
+		assert_eq!(
+			Some(0),
+			tileset.resolve_auto_tile("Wall", NeighborMask::NORTH)
+		);
+		assert_eq!(
+			Some(1),
+			tileset.resolve_auto_tile("Wall", NeighborMask::EAST)
+		);
+
+		// Without a fallback, the last tile is used
+		let tileset = build_walls(&[(north, false, None), (south, false, None)]);
+		assert_eq!(
+			Some(1),
+			tileset.resolve_auto_tile("Wall", NeighborMask::EAST)
+		);
+	}
+
+	#[cfg(debug_assertions)]
+	#[test]
+	fn should_warn_once_per_tileset() {
+		let north = edge_rule(0b0001);
+		let tileset = build_walls(&[(north, false, None)]);
+		let other = build_walls(&[(north, false, None)]);
+
+		tileset.resolve_auto_tile("Wall", NeighborMask::EAST);
+		tileset.resolve_auto_tile("Wall", NeighborMask::EAST);
+		tileset.resolve_auto_tile("Wall", NeighborMask::NORTH);
+		assert_eq!(1, tileset.unmatched_warnings.lock().unwrap().len());
+		assert!(other.unmatched_warnings.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn should_resolve_edge_masks() {
+		let autos = (0..16)
+			.map(|edges| (edge_rule(edges), false, None))
+			.collect::<Vec<_>>();
+		let tileset = build_walls(&autos);
+
+		let cases = [
+			(NeighborMask::NONE, 0),
+			(NeighborMask::NORTH, 0b0001),
+			(NeighborMask::EAST, 0b0010),
+			(NeighborMask::SOUTH, 0b0100),
+			(NeighborMask::WEST, 0b1000),
+			(NeighborMask(0b0101), 0b0101),
+			(NeighborMask(0b1010), 0b1010),
+			(NeighborMask::EDGES, 0b1111),
+			// Corners are ignored by edge rules
+			(NeighborMask::CORNERS, 0),
+			(
+				NeighborMask::WEST.with(NeighborMask::NORTH_EAST, true),
+				0b1000,
+			),
+			(NeighborMask(0xFF), 0b1111),
+		];
+		for (neighbors, expected) in cases {
+			assert_eq!(
+				Some(expected),
+				tileset.resolve_auto_tile("Wall", neighbors),
+				"neighbors {:#010b}",
+				neighbors.0
+			);
+		}
+	}
+
+	#[test]
+	fn should_resolve_corner_masks() {
+		let autos = (0..16)
+			.map(|corners| (AutoTileRule::default(), false, Some(corners)))
+			.collect::<Vec<_>>();
+		let tileset = build_walls(&autos);
+
+		let cases = [
+			(NeighborMask::NONE, 0),
+			(NeighborMask::EDGES, 0),
+			// A corner is only filled if both of its edges match
+			(NeighborMask::CORNERS, 0),
+			(NeighborMask(0b0001_0001), 0),
+			(NeighborMask(0b0001_0011), 0b0001),
+			(
+				NeighborMask::EDGES.with(NeighborMask::SOUTH_EAST, true),
+				0b0010,
+			),
+			(
+				NeighborMask::EDGES.with(NeighborMask::SOUTH_WEST, true),
+				0b0100,
+			),
+			(
+				NeighborMask::EDGES.with(NeighborMask::NORTH_WEST, true),
+				0b1000,
+			),
+			(NeighborMask(0b1010_1111), 0b1010),
+			(NeighborMask(0xFF), 0b1111),
+		];
+		for (neighbors, expected) in cases {
+			assert_eq!(
+				Some(expected),
+				tileset.resolve_auto_tile("Wall", neighbors),
+				"neighbors {:#010b}",
+				neighbors.0
+			);
+		}
+	}
+
+	#[test]
+	fn should_resolve_marching_squares() {
+		let autos = (0..16)
+			.map(|corners| (AutoTileRule::default(), false, Some(corners)))
+			.collect::<Vec<_>>();
+		let tileset = build_walls(&autos);
+
+		// Each case as `[top_left, top_right, bottom_right, bottom_left]` and its corners
+		let cases = [
+			([false, false, false, false], 0b0000),
+			([true, false, false, false], 0b1000),
+			([false, true, false, false], 0b0001),
+			([false, false, true, false], 0b0010),
+			([false, false, false, true], 0b0100),
+			([false, true, true, false], 0b0011),
+			([true, false, true, false], 0b1010),
+			([true, true, false, true], 0b1101),
+			([true, true, true, true], 0b1111),
+		];
+		for (corners, expected) in cases {
+			assert_eq!(
+				Some(expected),
+				tileset.marching_squares("Wall", corners),
+				"corners {:?}",
+				corners
+			);
+		}
+	}
+}
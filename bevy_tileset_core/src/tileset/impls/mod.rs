@@ -1,6 +1,9 @@
 //! Implementation details for [`Tileset`] and [`RawTileset`]
 
-use bevy::prelude::{Handle, Image, TextureAtlas, Vec2};
+use bevy::prelude::{Assets, Handle, Image, Rect, TextureAtlas, TextureAtlasSprite, Vec2};
+use image::ImageFormat;
+use ron::Value;
+use std::{collections::HashMap, path::Path};
 
 #[cfg(feature = "auto-tile")]
 pub use auto::*;
@@ -38,6 +41,34 @@ macro_rules! impl_tileset {
 				self.tile_size
 			}
 
+			/// Gets the ratio of pixels covered by tiles to the total pixels of the atlas texture
+			///
+			/// This is `1.0` when tiles fill the entire atlas. Padding, extrusion, power-of-two
+			/// sizing, empty cells in the last row, and tiles smaller than their cell (mixed sizes
+			/// or trimmed borders) all lower it.
+			pub fn atlas_efficiency(&self) -> f32 { self.atlas_efficiency }
+
+			/// Gets the options this tileset was built with
+			pub fn options(&self) -> &TilesetOptions { &self.options }
+
+			/// Checks whether tile names are looked up regardless of their casing
+			pub fn is_case_insensitive(&self) -> bool { self.case_insensitive }
+
+			/// Sets whether tile names are looked up regardless of their casing
+			///
+			/// Names are still stored (and displayed) with their original casing. Exact matches
+			/// are always preferred over case-insensitive ones.
+			///
+			/// # Arguments
+			///
+			/// * `case_insensitive`: Whether to ignore casing when looking up tiles by name
+			///
+			/// returns: ()
+			///
+			pub fn set_case_insensitive(&mut self, case_insensitive: bool) {
+				self.case_insensitive = case_insensitive;
+			}
+
 			/// Get the name of a tile by its group ID
 			///
 			/// # Arguments
@@ -72,7 +103,24 @@ macro_rules! impl_tileset {
 			/// returns: Option<&u32>
 			///
 			pub fn get_tile_group_id(&self, name: &str) -> Option<&TileGroupId> {
-				self.tile_ids.get(name)
+				self.find_by_name(&self.tile_ids, name)
+			}
+
+			/// Get the [`TileId`] of a tile by its name
+			///
+			/// The returned ID is cheap to copy and remains stable for the lifetime of this tileset.
+			/// It can be passed to methods such as [`get_tile_index_by_id`](Self::get_tile_index_by_id)
+			/// to look up a tile without hashing its name again.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<TileId>
+			///
+			pub fn tile_id(&self, name: &str) -> Option<TileId> {
+				let group_id = self.get_tile_group_id(name)?;
+				Some(TileId::new(*group_id, self.id))
 			}
 
 			/// Get the ID of a tile by its index in the texture atlas
@@ -99,6 +147,244 @@ macro_rules! impl_tileset {
 				self.tile_handles.get(index)
 			}
 
+			/// Get the user data attached to a tile by its name
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&Value>
+			///
+			pub fn get_tile_metadata(&self, name: &str) -> Option<&Value> {
+				self.get_tile_data(name)?.metadata()
+			}
+
+			/// Get the transform applied to a tile by its name
+			///
+			/// This is only ever not the identity transform for tiles defined as a
+			/// [`TileDefType::Reference`] to another tile.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&TileTransform>
+			///
+			pub fn get_tile_transform(&self, name: &str) -> Option<&TileTransform> {
+				Some(self.get_tile_data(name)?.transform())
+			}
+
+			/// Creates a `TextureAtlasSprite` for the tile with the given name
+			///
+			/// The sprite uses the tile's base index and has its [`TileTransform`] flips applied.
+			/// Since sprites cannot be rotated themselves, any rotation should be applied to the
+			/// entity's `Transform` using [`TileRotation::radians`] (clockwise). Trimmed tiles are
+			/// anchored so that they line up with their untrimmed texture (see [`TileTrim::anchor`]).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<TextureAtlasSprite>
+			///
+			pub fn tile_sprite(&self, name: &str) -> Option<TextureAtlasSprite> {
+				self.tile_sprite_at(name, self.get_base_tile_index(name)?)
+			}
+
+			/// Creates a `TextureAtlasSprite` displaying the given atlas index of a tile
+			///
+			/// This allows a selected variant to be displayed while still applying the tile's
+			/// flips and the trim anchor of that index (see [`Self::tile_sprite`]).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			/// * `index`: The atlas index to display
+			///
+			/// returns: Option<TextureAtlasSprite>
+			///
+			pub(crate) fn tile_sprite_at(
+				&self,
+				name: &str,
+				index: usize,
+			) -> Option<TextureAtlasSprite> {
+				let transform = self.get_tile_transform(name)?;
+				let mut sprite = TextureAtlasSprite::new(index);
+				sprite.flip_x = transform.flip_x;
+				sprite.flip_y = transform.flip_y;
+				if let Some(trim) = self.get_trim(&index) {
+					sprite.anchor = trim.anchor();
+				}
+				Some(sprite)
+			}
+
+			/// Get the collision shape of a tile by its name
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&TileCollision>
+			///
+			pub fn get_tile_collision(&self, name: &str) -> Option<&TileCollision> {
+				self.get_tile_data(name)?.collision()
+			}
+
+			/// Iterate over the names of all tiles with the given tag
+			///
+			/// The iteration order is unspecified.
+			///
+			/// # Arguments
+			///
+			/// * `tag`: The tag to search for
+			///
+			/// returns: impl Iterator<Item=&str>
+			///
+			pub fn tiles_with_tag<'a>(
+				&'a self,
+				tag: &'a str,
+			) -> impl Iterator<Item = &'a str> + 'a {
+				self.find_tiles(move |tile| tile.has_tag(tag))
+			}
+
+			/// Iterate over the names of all tile groups in this tileset
+			///
+			/// Each group is only returned once, in alphabetical order.
+			///
+			/// returns: impl Iterator<Item=&str>
+			///
+			pub fn groups(&self) -> impl Iterator<Item = &str> {
+				self.tiles
+					.values()
+					.filter_map(|tile| tile.group())
+					.collect::<std::collections::BTreeSet<_>>()
+					.into_iter()
+			}
+
+			/// Iterate over the names of all tiles in the given group
+			///
+			/// The iteration order is unspecified.
+			///
+			/// # Arguments
+			///
+			/// * `group`: The group to search for
+			///
+			/// returns: impl Iterator<Item=&str>
+			///
+			pub fn tiles_in_group<'a>(
+				&'a self,
+				group: &'a str,
+			) -> impl Iterator<Item = &'a str> + 'a {
+				self.find_tiles(move |tile| tile.group() == Some(group))
+			}
+
+			/// Iterate over the names of all tiles matching the given predicate
+			///
+			/// The iteration order is unspecified.
+			///
+			/// # Arguments
+			///
+			/// * `predicate`: The predicate each tile's [`TileData`] is checked against
+			///
+			/// returns: impl Iterator<Item=&str>
+			///
+			/// # Examples
+			///
+			/// ```
+			/// let animated: Vec<&str> = tileset.find_tiles(|tile| tile.is_animated()).collect();
+			/// ```
+			pub fn find_tiles<'a, F: Fn(&TileData) -> bool + 'a>(
+				&'a self,
+				predicate: F,
+			) -> impl Iterator<Item = &'a str> + 'a {
+				self.tiles
+					.values()
+					.filter(move |tile| predicate(tile))
+					.map(|tile| tile.name())
+			}
+
+			/// Get the explicit duration of an animation frame by its index in the texture atlas
+			///
+			/// Returns `None` if the frame was not given its own duration, in which case
+			/// the animation's default timing should be used.
+			///
+			/// # Arguments
+			///
+			/// * `index`: The frame's index
+			///
+			/// returns: Option<f32>
+			///
+			pub fn get_frame_duration(&self, index: &usize) -> Option<f32> {
+				self.frame_durations.get(index).copied()
+			}
+
+			/// Get the borders that were trimmed from a tile's texture by its index in the texture atlas
+			///
+			/// Returns `None` if the tile was not trimmed.
+			///
+			/// # Arguments
+			///
+			/// * `index`: The tile's index
+			///
+			/// returns: Option<&TileTrim>
+			///
+			pub fn get_trim(&self, index: &usize) -> Option<&TileTrim> { self.trims.get(index) }
+
+			/// Get the number of frames in an animated tile by its name
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<usize>
+			///
+			pub fn animation_frame_count(&self, name: &str) -> Option<usize> {
+				match self.get_tile_data(name)?.tile() {
+					TileType::Animated(anim) => Some(anim.frame_count()),
+					_ => None,
+				}
+			}
+
+			/// Get the total duration (in seconds) of an animated tile by its name
+			///
+			/// This is the sum of each frame's duration. Frames without an explicit duration
+			/// are assumed to last `1 / speed` seconds.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<f32>
+			///
+			pub fn animation_duration(&self, name: &str) -> Option<f32> {
+				match self.get_tile_data(name)?.tile() {
+					TileType::Animated(anim) => Some(
+						(anim.start()..=anim.end())
+							.map(|index| {
+								self.get_frame_duration(&index)
+									.unwrap_or_else(|| 1.0 / anim.speed())
+							})
+							.sum(),
+					),
+					_ => None,
+				}
+			}
+
+			/// Get the definition a tile was created from by its name
+			///
+			/// This is only available for tiles added from a [`TileDef`] (such as those loaded from
+			/// tile definition files), and will return `None` for all other tiles.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The tile's name
+			///
+			/// returns: Option<&TileDef>
+			///
+			pub fn get_tile_def(&self, name: &str) -> Option<&TileDef> {
+				self.find_by_name(&self.tile_defs, name)
+			}
+
 			/// Get the data of a tile by its name
 			///
 			/// # Arguments
@@ -108,7 +394,7 @@ macro_rules! impl_tileset {
 			/// returns: Option<&TileData>
 			///
 			pub fn get_tile_data(&self, name: &str) -> Option<&TileData> {
-				let id = self.tile_ids.get(name)?;
+				let id = self.get_tile_group_id(name)?;
 				self.tiles.get(id)
 			}
 
@@ -170,6 +456,92 @@ macro_rules! impl_tileset {
 				}
 			}
 
+			/// Gets every index into the `TextureAtlas` used by the tile with the given name
+			///
+			/// This includes all frames of an animated tile as well as those of every variant
+			/// or auto tile belonging to it. The indices are returned in ascending order.
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the tile
+			///
+			/// returns: Option<Vec<usize>>
+			///
+			/// # Examples
+			///
+			/// ```
+			/// let indices: Vec<usize> = tileset.get_tile_indices("My Tile").unwrap();
+			/// ```
+			pub fn get_tile_indices(&self, name: &str) -> Option<Vec<usize>> {
+				let data = self.get_tile_data(name)?;
+				let mut indices = self
+					.tile_indices
+					.keys()
+					.copied()
+					.filter(|index| data.tile().contains_index(index))
+					.collect::<Vec<_>>();
+				indices.sort_unstable();
+				Some(indices)
+			}
+
+			/// Looks up a value by tile name, falling back to a case-insensitive match if enabled
+			fn find_by_name<'a, T>(
+				&self,
+				map: &'a HashMap<String, T>,
+				name: &str,
+			) -> Option<&'a T> {
+				map.get(name).or_else(|| {
+					if !self.case_insensitive {
+						return None;
+					}
+					let name = name.to_lowercase();
+					map.iter()
+						.find(|(key, ..)| key.to_lowercase() == name)
+						.map(|(.., value)| value)
+				})
+			}
+
+			/// Maps each tile's name to the area of the atlas texture it covers
+			///
+			/// Tiles spanning multiple indices (animated, variant, and auto tiles) use their
+			/// lowest index.
+			fn collect_tile_rects<'a>(&'a self, rects: &[Rect]) -> HashMap<&'a str, Rect> {
+				self.tiles
+					.values()
+					.filter_map(|tile| {
+						let index = *self.get_tile_indices(tile.name())?.first()?;
+						Some((tile.name(), *rects.get(index)?))
+					})
+					.collect()
+			}
+
+			/// Saves this tileset's atlas texture as a PNG file
+			///
+			/// This is mainly useful for debugging or for baking the generated atlas.
+			///
+			/// # Arguments
+			///
+			/// * `textures`: The image assets containing the atlas texture
+			/// * `path`: The path of the file to write
+			///
+			/// returns: Result<(), TilesetError>
+			///
+			pub fn save_atlas_png<P: AsRef<Path>>(
+				&self,
+				textures: &Assets<Image>,
+				path: P,
+			) -> Result<(), TilesetError> {
+				let texture = textures
+					.get(self.texture())
+					.ok_or(TilesetError::ImageNotFound)?;
+				texture
+					.clone()
+					.try_into_dynamic()
+					.map_err(|err| TilesetError::AtlasExportError(err.to_string()))?
+					.save_with_format(path, ImageFormat::Png)
+					.map_err(|err| TilesetError::AtlasExportError(err.to_string()))
+			}
+
 			/// Select a tile by its name
 			///
 			/// If the tile is a Variant tile, a random variant will be chosen.
@@ -247,6 +619,28 @@ impl RawTileset {
 	pub fn texture(&self) -> &Handle<Image> {
 		&self.atlas.texture
 	}
+
+	/// Gets the area of the atlas texture covered by the tile with the given name
+	///
+	/// For animated, variant, and auto tiles, this is the area of the tile's base index.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile
+	///
+	/// returns: Option<Rect>
+	///
+	pub fn get_tile_rect(&self, name: &str) -> Option<Rect> {
+		let index = self.get_base_tile_index(name)?;
+		self.atlas.textures.get(index).copied()
+	}
+
+	/// Gets the area of the atlas texture covered by every tile, mapped by the tile's name
+	///
+	/// Tiles spanning multiple indices (animated, variant, and auto tiles) use their lowest index.
+	pub fn tile_rects(&self) -> HashMap<&str, Rect> {
+		self.collect_tile_rects(&self.atlas.textures)
+	}
 }
 
 impl Tileset {
@@ -259,4 +653,34 @@ impl Tileset {
 	pub fn texture(&self) -> &Handle<Image> {
 		&self.texture
 	}
+
+	/// Gets the area of the atlas texture covered by the tile with the given name
+	///
+	/// For animated, variant, and auto tiles, this is the area of the tile's base index.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tile
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	///
+	/// returns: Option<Rect>
+	///
+	pub fn get_tile_rect(&self, name: &str, atlases: &Assets<TextureAtlas>) -> Option<Rect> {
+		let index = self.get_base_tile_index(name)?;
+		atlases.get(&self.atlas)?.textures.get(index).copied()
+	}
+
+	/// Gets the area of the atlas texture covered by every tile, mapped by the tile's name
+	///
+	/// Tiles spanning multiple indices (animated, variant, and auto tiles) use their lowest index.
+	///
+	/// # Arguments
+	///
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	///
+	/// returns: Option<HashMap<&str, Rect>>
+	///
+	pub fn tile_rects(&self, atlases: &Assets<TextureAtlas>) -> Option<HashMap<&str, Rect>> {
+		Some(self.collect_tile_rects(&atlases.get(&self.atlas)?.textures))
+	}
 }
@@ -3,7 +3,8 @@
 use crate::prelude::{RawTileset, Tileset};
 use bevy_tileset_tiles::prelude::*;
 use rand::distributions::{Distribution, WeightedIndex};
-use rand::thread_rng;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 macro_rules! impl_tileset {
 	($name: ident) => {
@@ -16,11 +17,72 @@ macro_rules! impl_tileset {
 			///
 			/// returns: Option<&VariantTileData>
 			pub fn select_variant(variants: &[VariantTileData]) -> Option<&VariantTileData> {
-				let mut rng = thread_rng();
+				Self::select_variant_with_rng(variants, &mut thread_rng())
+			}
+
+			/// Randomly selects a variant from a collection of variants based on their weights,
+			/// using the given random number generator
+			///
+			/// # Arguments
+			///
+			/// * `variants`: The variants to choose from
+			/// * `rng`: The random number generator to sample with
+			///
+			/// returns: Option<&VariantTileData>
+			pub fn select_variant_with_rng<'a, R: Rng + ?Sized>(
+				variants: &'a [VariantTileData],
+				rng: &mut R,
+			) -> Option<&'a VariantTileData> {
+				let idx = Self::sample_variant(variants, rng)?;
+				variants.get(idx)
+			}
+
+			/// Randomly selects the index of a variant for the variant tile with the given name
+			///
+			/// Variants are sampled according to their weights (which default to `1.0`).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the variant tile
+			/// * `rng`: The random number generator to sample with
+			///
+			/// returns: Option<usize>
+			pub fn select_variant_index<R: Rng + ?Sized>(
+				&self,
+				name: &str,
+				rng: &mut R,
+			) -> Option<usize> {
+				match self.get_tile_data(name)?.tile() {
+					TileType::Variant(variants) => Self::sample_variant(variants, rng),
+					_ => None,
+				}
+			}
+
+			/// Selects the index of a variant for the variant tile with the given name, using the
+			/// given seed
+			///
+			/// The selection is a pure function of the tileset, `name`, and `seed`, making it
+			/// suitable for networked or replayed games (e.g. seeding with a hash of the tile's
+			/// world position).
+			///
+			/// # Arguments
+			///
+			/// * `name`: The name of the variant tile
+			/// * `seed`: The seed to sample with
+			///
+			/// returns: Option<usize>
+			pub fn select_variant_seeded(&self, name: &str, seed: u64) -> Option<usize> {
+				self.select_variant_index(name, &mut ChaCha8Rng::seed_from_u64(seed))
+			}
+
+			/// Samples the index of a variant based on the variants' weights
+			fn sample_variant<R: Rng + ?Sized>(
+				variants: &[VariantTileData],
+				rng: &mut R,
+			) -> Option<usize> {
 				let weights: Vec<f32> = variants.iter().map(|variant| variant.weight()).collect();
 				let dist = WeightedIndex::new(weights).ok()?;
-				let idx = dist.sample(&mut rng);
-				variants.get(idx)
+				Some(dist.sample(rng))
 			}
 		}
 	};
@@ -28,3 +90,73 @@ macro_rules! impl_tileset {
 
 impl_tileset!(Tileset);
 impl_tileset!(RawTileset);
+
+#[cfg(test)]
+mod tests {
+	use bevy::prelude::{Assets, Image};
+
+	use super::*;
+	use crate::{
+		prelude::{TilesetBuilder, TilesetId},
+		tileset::fixtures::solid_image,
+	};
+
+	/// Builds a tileset with a `"Grass"` variant tile with the given weights and a `"Dirt"`
+	/// standard tile
+	fn build_grass(weights: &[f32]) -> RawTileset {
+		let mut textures = Assets::<Image>::default();
+		let variants = weights
+			.iter()
+			.map(|weight| VariantTileHandle {
+				weight: *weight,
+				tile: SimpleTileHandle::Standard(textures.add(solid_image(2, 2))),
+			})
+			.collect();
+		let mut builder = TilesetBuilder::default();
+		builder
+			.add_tile(TileHandle::new_variant("Grass", variants), 0, &textures)
+			.unwrap();
+		let dirt = TileHandle::new_standard("Dirt", textures.add(solid_image(2, 2)));
+		builder.add_tile(dirt, 1, &textures).unwrap();
+		builder.build("Test", TilesetId(0), &mut textures).unwrap()
+	}
+
+	#[test]
+	fn should_select_seeded_variants_deterministically() {
+		let tileset = build_grass(&[1.0, 1.0, 1.0, 1.0]);
+		let other = build_grass(&[1.0, 1.0, 1.0, 1.0]);
+
+		let selected = (0..64)
+			.map(|seed| tileset.select_variant_seeded("Grass", seed).unwrap())
+			.collect::<Vec<_>>();
+		for (seed, index) in selected.iter().enumerate() {
+			assert_eq!(Some(*index), tileset.select_variant_seeded("Grass", seed as u64));
+			assert_eq!(Some(*index), other.select_variant_seeded("Grass", seed as u64));
+		}
+		// Different seeds should still select different variants
+		assert!(selected.iter().any(|index| *index != selected[0]));
+
+		assert_eq!(None, tileset.select_variant_seeded("Dirt", 0));
+		assert_eq!(None, tileset.select_variant_seeded("Missing", 0));
+	}
+
+	#[test]
+	fn should_select_variants_by_weight() {
+		let tileset = build_grass(&[3.0, 1.0, 0.0]);
+		let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+		let mut counts = [0; 3];
+		for _ in 0..4000 {
+			counts[tileset.select_variant_index("Grass", &mut rng).unwrap()] += 1;
+		}
+		assert_eq!(0, counts[2], "zero-weight variants should never be selected");
+		assert!((2800..3200).contains(&counts[0]), "{:?}", counts);
+		assert!((800..1200).contains(&counts[1]), "{:?}", counts);
+	}
+
+	#[test]
+	fn should_not_select_variants_without_weight() {
+		let tileset = build_grass(&[0.0, 0.0]);
+		assert_eq!(None, tileset.select_variant_seeded("Grass", 0));
+	}
+}
@@ -0,0 +1,202 @@
+//! Serializable layout of a built tileset
+
+use std::collections::HashMap;
+
+use bevy::prelude::{Handle, Image, Rect, TextureAtlas, Vec2};
+use bevy_tileset_tiles::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::*, tileset::atlas::atlas_efficiency};
+
+/// The computed layout of a [`Tileset`], without any of its textures
+///
+/// Building a tileset requires reading every tile definition and texture. This structure
+/// contains everything computed during that process so that it can be cached (e.g. to disk)
+/// and later re-bound to a separately loaded atlas texture using [`Tileset::restore`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TilesetLayout {
+	/// The ID of the tileset
+	pub id: TilesetId,
+	/// The name of the tileset
+	pub name: String,
+	/// The registered tiles mapped by their ID
+	pub tiles: HashMap<TileGroupId, TileData>,
+	/// The size of the tileset (in pixels)
+	pub size: Vec2,
+	/// The size of the tiles in the tileset (in pixels)
+	pub tile_size: Vec2,
+	/// The tile group IDs mapped by their name
+	pub tile_ids: HashMap<String, TileGroupId>,
+	/// The tile names mapped by their ID
+	pub tile_names: HashMap<TileGroupId, String>,
+	/// The tile IDs mapped by their index in the atlas
+	pub tile_indices: HashMap<usize, TileId>,
+	/// The explicit durations of animation frames (in seconds) mapped by their index in the atlas
+	#[serde(default)]
+	pub frame_durations: HashMap<usize, f32>,
+	/// The borders trimmed from tile textures mapped by their index in the atlas
+	#[serde(default)]
+	pub trims: HashMap<usize, TileTrim>,
+	/// The definitions of tiles added from a [`TileDef`] mapped by their name
+	#[serde(default)]
+	pub tile_defs: HashMap<String, TileDef>,
+	/// Whether tile names are matched regardless of their casing
+	#[serde(default)]
+	pub case_insensitive: bool,
+	/// The options the tileset was built with
+	#[serde(default)]
+	pub options: TilesetOptions,
+	/// The area of the atlas texture covered by each tile, ordered by index
+	pub rects: Vec<Rect>,
+}
+
+impl TilesetLayout {
+	/// Create a `TextureAtlas` for the given texture using this layout
+	///
+	/// # Arguments
+	///
+	/// * `texture`: The handle to the atlas texture
+	///
+	/// returns: TextureAtlas
+	///
+	pub fn texture_atlas(&self, texture: Handle<Image>) -> TextureAtlas {
+		let mut atlas = TextureAtlas::new_empty(texture, self.size);
+		for rect in &self.rects {
+			atlas.add_texture(*rect);
+		}
+		atlas
+	}
+}
+
+impl Tileset {
+	/// Gets the computed layout of this tileset
+	///
+	/// # Arguments
+	///
+	/// * `atlas`: This tileset's `TextureAtlas`
+	///
+	/// returns: TilesetLayout
+	///
+	pub fn layout(&self, atlas: &TextureAtlas) -> TilesetLayout {
+		TilesetLayout {
+			id: self.id,
+			name: self.name.clone(),
+			tiles: self.tiles.clone(),
+			size: self.size,
+			tile_size: self.tile_size,
+			tile_ids: self.tile_ids.clone(),
+			tile_names: self.tile_names.clone(),
+			tile_indices: self.tile_indices.clone(),
+			frame_durations: self.frame_durations.clone(),
+			trims: self.trims.clone(),
+			tile_defs: self.tile_defs.clone(),
+			case_insensitive: self.case_insensitive,
+			options: self.options.clone(),
+			rects: atlas.textures.clone(),
+		}
+	}
+
+	/// Restore a tileset from a previously computed layout
+	///
+	/// Since the individual tile textures are not part of the layout, the restored tileset will
+	/// not contain any tile handles (i.e. [`Tileset::get_tile_handle`] will always return `None`).
+	///
+	/// # Arguments
+	///
+	/// * `layout`: The computed layout
+	/// * `atlas`: The handle to the `TextureAtlas` (see [`TilesetLayout::texture_atlas`])
+	/// * `texture`: The handle to the atlas texture
+	///
+	/// returns: Tileset
+	///
+	pub fn restore(
+		layout: TilesetLayout,
+		atlas: Handle<TextureAtlas>,
+		texture: Handle<Image>,
+	) -> Self {
+		let atlas_efficiency =
+			atlas_efficiency(&layout.rects, layout.tile_indices.keys(), layout.size);
+		Self {
+			id: layout.id,
+			name: layout.name,
+			tiles: layout.tiles,
+			size: layout.size,
+			tile_size: layout.tile_size,
+			tile_ids: layout.tile_ids,
+			tile_names: layout.tile_names,
+			tile_handles: HashMap::new(),
+			tile_indices: layout.tile_indices,
+			frame_durations: layout.frame_durations,
+			trims: layout.trims,
+			atlas_efficiency,
+			tile_defs: layout.tile_defs,
+			case_insensitive: layout.case_insensitive,
+			options: layout.options,
+			atlas,
+			texture,
+			#[cfg(all(feature = "auto-tile", debug_assertions))]
+			unmatched_warnings: Default::default(),
+		}
+	}
+}
+
+impl RawTileset {
+	/// Gets the computed layout of this tileset
+	pub fn layout(&self) -> TilesetLayout {
+		TilesetLayout {
+			id: self.id,
+			name: self.name.clone(),
+			tiles: self.tiles.clone(),
+			size: self.size,
+			tile_size: self.tile_size,
+			tile_ids: self.tile_ids.clone(),
+			tile_names: self.tile_names.clone(),
+			tile_indices: self.tile_indices.clone(),
+			frame_durations: self.frame_durations.clone(),
+			trims: self.trims.clone(),
+			tile_defs: self.tile_defs.clone(),
+			case_insensitive: self.case_insensitive,
+			options: self.options.clone(),
+			rects: self.atlas.textures.clone(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::prelude::Assets;
+
+	use super::*;
+	use crate::tileset::fixtures::solid_image;
+
+	#[test]
+	fn should_restore_serialized_layout() {
+		let mut textures = Assets::<Image>::default();
+		let entries = ["Grass", "Dirt", "Wall"]
+			.into_iter()
+			.map(|name| (name.to_string(), textures.add(solid_image(2, 2))))
+			.collect();
+		let mut builder = TilesetBuilder::new(Some(2));
+		builder.padding(1);
+		let raw_tileset = builder
+			.build_from_textures("Test", TilesetId(3), entries, &mut textures)
+			.unwrap();
+
+		let serialized = ron::to_string(&raw_tileset.layout()).unwrap();
+		let layout = ron::from_str::<TilesetLayout>(&serialized).unwrap();
+		let atlas = layout.texture_atlas(Handle::default());
+		let tileset = Tileset::restore(layout, Handle::default(), Handle::default());
+
+		assert_eq!(raw_tileset.id(), tileset.id());
+		assert_eq!(raw_tileset.name(), tileset.name());
+		for name in ["Grass", "Dirt", "Wall"] {
+			let index = raw_tileset.get_base_tile_index(name);
+			assert!(index.is_some());
+			assert_eq!(index, tileset.get_base_tile_index(name));
+			let group_id = tileset.get_tile_group_id(name).unwrap();
+			assert_eq!(Some(&name.to_string()), tileset.get_tile_name(group_id));
+		}
+		assert_eq!(raw_tileset.atlas().size, atlas.size);
+		assert_eq!(raw_tileset.atlas().textures, atlas.textures);
+	}
+}
@@ -0,0 +1,373 @@
+//! Importing tilesets from [LDtk](https://ldtk.io/) projects
+//!
+//! Only accessible with the `ldtk` feature enabled
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	path::PathBuf,
+};
+
+use bevy::{
+	asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, Handle, LoadContext},
+	log::warn,
+	prelude::{AssetId, FromWorld, Image, World},
+	render::texture::CompressedImageFormats,
+};
+use bevy_tileset_tiles::prelude::TileHandle;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	prelude::{TileGroupId, Tileset, TilesetBuilder, TilesetError, TilesetId},
+	tileset::asset::{
+		case_insensitive,
+		finalize_tileset,
+		load_image,
+		slice_image,
+		supported_compressed_formats,
+		TilesetTextureStore,
+	},
+};
+
+/// An asset loader for the tilesets defined in an LDtk project (`.ldtk`)
+///
+/// Each tileset in the project's `defs.tilesets` is sliced along its grid and built into a
+/// `Tileset`. The first tileset is the main asset, while the rest are added as labeled assets
+/// using their LDtk identifier as the label (e.g. `"world.ldtk#Terrain"`). Tileset IDs are
+/// assigned in the order the tilesets are defined in the project, starting at
+/// [`LdtkLoaderSettings::first_id`].
+///
+/// Tiles are named after the LDtk enum value they are tagged with. If an enum value tags multiple
+/// tiles, the tile ID is appended to keep names unique (e.g. `"Grass 12"`). Untagged tiles are
+/// named by their tile ID. Custom data is parsed as RON if possible (falling back to a string)
+/// and stored as the tile's metadata.
+pub(crate) struct LdtkTilesetLoader {
+	supported_compressed_formats: CompressedImageFormats,
+	case_insensitive: bool,
+}
+
+/// The settings for loading the tilesets of an LDtk project
+///
+/// Tilesets are registered by their ID, so every project should be given its own range of IDs
+/// that doesn't overlap with other projects or tilesets:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+/// fn load_project(asset_server: Res<AssetServer>) {
+/// 	let handle: Handle<Tileset> =
+/// 		asset_server.load_with_settings("world.ldtk", |settings: &mut LdtkLoaderSettings| {
+/// 			settings.first_id = 100;
+/// 		});
+/// }
+/// ```
+///
+/// Only accessible with the `ldtk` feature enabled
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct LdtkLoaderSettings {
+	/// The ID of the first tileset in the project
+	///
+	/// The remaining tilesets are numbered up from this in the order they are defined.
+	pub first_id: u32,
+}
+
+/// The parts of an LDtk project needed to load its tilesets
+#[derive(Deserialize)]
+struct LdtkProject {
+	defs: LdtkDefinitions,
+}
+
+#[derive(Deserialize)]
+struct LdtkDefinitions {
+	tilesets: Vec<LdtkTileset>,
+}
+
+/// An LDtk tileset definition
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LdtkTileset {
+	identifier: String,
+	/// The path to the tileset image, relative to the project file
+	///
+	/// This is `None` for LDtk's embedded tilesets
+	rel_path: Option<String>,
+	px_wid: u32,
+	px_hei: u32,
+	tile_grid_size: u32,
+	#[serde(default)]
+	spacing: u32,
+	/// The space around the edges of the image
+	#[serde(default)]
+	padding: u32,
+	#[serde(default)]
+	enum_tags: Vec<LdtkEnumTag>,
+	#[serde(default)]
+	custom_data: Vec<LdtkCustomData>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LdtkEnumTag {
+	enum_value_id: String,
+	tile_ids: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LdtkCustomData {
+	tile_id: u32,
+	data: String,
+}
+
+impl FromWorld for LdtkTilesetLoader {
+	fn from_world(world: &mut World) -> Self {
+		Self {
+			supported_compressed_formats: supported_compressed_formats(world),
+			case_insensitive: case_insensitive(world),
+		}
+	}
+}
+
+impl AssetLoader for LdtkTilesetLoader {
+	type Asset = Tileset;
+	type Settings = LdtkLoaderSettings;
+	type Error = TilesetError;
+
+	fn load<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		settings: &'a Self::Settings,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			reader.read_to_end(&mut bytes).await?;
+			let project = serde_json::from_slice::<LdtkProject>(&bytes)
+				.map_err(|err| TilesetError::InvalidLdtk(err.to_string()))?;
+
+			let mut main = None;
+			for (index, def) in project.defs.tilesets.into_iter().enumerate() {
+				let Some(rel_path) = def.rel_path.as_ref() else {
+					warn!("skipping embedded LDtk tileset {:?}", def.identifier);
+					continue;
+				};
+				let path = match load_context.path().parent() {
+					Some(parent) => parent.join(rel_path),
+					None => PathBuf::from(rel_path),
+				};
+				let (.., image) = load_image(
+					load_context,
+					AssetId::default(),
+					path,
+					self.supported_compressed_formats,
+				)
+				.await?;
+
+				let identifier = def.identifier.clone();
+				let id = u32::try_from(index)
+					.ok()
+					.and_then(|index| settings.first_id.checked_add(index))
+					.ok_or_else(|| {
+						TilesetError::InvalidLdtk(format!(
+							"tileset ID of {:?} overflows",
+							identifier
+						))
+					})?;
+				let mut tileset = load_tileset(def, TilesetId(id), &image, load_context)?;
+				tileset.set_case_insensitive(self.case_insensitive);
+				if main.is_none() {
+					main = Some(tileset);
+				} else {
+					load_context.add_labeled_asset(identifier, tileset);
+				}
+			}
+
+			main.ok_or_else(|| TilesetError::InvalidLdtk("project has no tilesets".to_string()))
+		})
+	}
+
+	fn extensions(&self) -> &[&str] { &["ldtk"] }
+}
+
+/// Slice the given tileset image and build it into a [`Tileset`]
+fn load_tileset(
+	def: LdtkTileset,
+	id: TilesetId,
+	image: &Image,
+	load_context: &mut LoadContext,
+) -> Result<Tileset, TilesetError> {
+	// === Collect Tile Data === //
+	let mut names = BTreeMap::new();
+	for tag in &def.enum_tags {
+		for tile_id in &tag.tile_ids {
+			let name = if tag.tile_ids.len() > 1 {
+				format!("{} {}", tag.enum_value_id, tile_id)
+			} else {
+				tag.enum_value_id.clone()
+			};
+			names.entry(*tile_id).or_insert(name);
+		}
+	}
+	let metadata = def
+		.custom_data
+		.iter()
+		.map(|data| {
+			let value = ron::from_str::<ron::Value>(&data.data)
+				.unwrap_or_else(|_| ron::Value::String(data.data.clone()));
+			(data.tile_id, value)
+		})
+		.collect::<HashMap<_, _>>();
+
+	// === Slice Tiles === //
+	let (columns, tiles) = slice_tiles(&def, image)?;
+	let mut images = HashMap::with_capacity(tiles.len());
+	let mut handles = Vec::with_capacity(tiles.len());
+	for (tile_id, tile) in tiles {
+		let handle: Handle<Image> =
+			load_context.get_label_handle(format!("{}__tile_{}", def.identifier, tile_id));
+		images.insert(handle.clone(), tile);
+		handles.push((tile_id, handle));
+	}
+
+	// === Build Tiles === //
+	let mut store = TilesetTextureStore {
+		load_context,
+		images,
+	};
+	let mut builder = TilesetBuilder::new(Some(columns as usize));
+	builder.padding(def.spacing);
+	for (tile_id, handle) in handles {
+		let name = names
+			.get(&tile_id)
+			.cloned()
+			.unwrap_or_else(|| tile_id.to_string());
+		let mut tile_handle = TileHandle::new_standard(name, handle);
+		tile_handle.metadata = metadata.get(&tile_id).cloned();
+		builder.add_tile(tile_handle, tile_id as TileGroupId, &store)?;
+	}
+
+	let atlas_label = format!("{}__atlas", def.identifier);
+	let raw_tileset = builder.build(def.identifier, id, &mut store)?;
+	Ok(finalize_tileset(
+		raw_tileset,
+		&atlas_label,
+		store.load_context,
+	))
+}
+
+/// Slice the given tileset image along the grid of its definition
+///
+/// returns: Result<(u32, Vec<(u32, Image)>), TilesetError> (the number of columns along with the
+/// tiles and their LDtk tile IDs)
+fn slice_tiles(def: &LdtkTileset, image: &Image) -> Result<(u32, Vec<(u32, Image)>), TilesetError> {
+	let grid = def.tile_grid_size;
+	if grid == 0 {
+		return Err(TilesetError::InvalidLdtk(format!(
+			"tileset {:?} has no grid size",
+			def.identifier
+		)));
+	}
+	let count = |size: u32| {
+		(size.saturating_sub(def.padding.saturating_mul(2)) + def.spacing) / (grid + def.spacing)
+	};
+	let (columns, rows) = (count(def.px_wid), count(def.px_hei));
+
+	let mut tiles = Vec::with_capacity((columns * rows) as usize);
+	for tile_id in 0..columns * rows {
+		let x = def.padding + (tile_id % columns) * (grid + def.spacing);
+		let y = def.padding + (tile_id / columns) * (grid + def.spacing);
+		let tile = slice_image(image, x, y, grid, grid).ok_or_else(|| {
+			TilesetError::InvalidLdtk(format!("tile {} is outside of the image", tile_id))
+		})?;
+		tiles.push((tile_id, tile));
+	}
+	Ok((columns, tiles))
+}
+
+#[cfg(test)]
+mod tests {
+	use bevy::{
+		prelude::Image,
+		render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+	};
+
+	use super::{slice_tiles, LdtkProject, LdtkTileset};
+
+	#[test]
+	fn should_parse_ldtk_tilesets() {
+		let json = r#"{
+			"jsonVersion": "1.5.3",
+			"defs": {
+				"layers": [],
+				"tilesets": [{
+					"identifier": "Terrain",
+					"uid": 1,
+					"relPath": "terrain.png",
+					"pxWid": 32,
+					"pxHei": 16,
+					"tileGridSize": 16,
+					"spacing": 0,
+					"padding": 0,
+					"enumTags": [{ "enumValueId": "Water", "tileIds": [1] }],
+					"customData": [{ "tileId": 1, "data": "(depth: 3)" }]
+				}]
+			}
+		}"#;
+		let project = serde_json::from_str::<LdtkProject>(json).unwrap();
+		let tileset = &project.defs.tilesets[0];
+
+		assert_eq!("Terrain", tileset.identifier);
+		assert_eq!(Some("terrain.png"), tileset.rel_path.as_deref());
+		assert_eq!(16, tileset.tile_grid_size);
+		assert_eq!(vec![1], tileset.enum_tags[0].tile_ids);
+		assert_eq!("(depth: 3)", tileset.custom_data[0].data);
+	}
+
+	#[test]
+	fn should_slice_tiles_along_grid() {
+		// Two columns and rows of 2x2 tiles with a padding of 1 and a spacing of 2
+		let def = LdtkTileset {
+			identifier: String::from("Terrain"),
+			rel_path: None,
+			px_wid: 8,
+			px_hei: 8,
+			tile_grid_size: 2,
+			spacing: 2,
+			padding: 1,
+			enum_tags: Vec::new(),
+			custom_data: Vec::new(),
+		};
+		// Each pixel stores its own position to check which pixels were sliced
+		let data = (0..8u8)
+			.flat_map(|y| (0..8u8).flat_map(move |x| [x, y, 0, 255]))
+			.collect();
+		let image = Image::new(
+			Extent3d {
+				width: 8,
+				height: 8,
+				..Default::default()
+			},
+			TextureDimension::D2,
+			data,
+			TextureFormat::Rgba8UnormSrgb,
+		);
+
+		let (columns, tiles) = slice_tiles(&def, &image).unwrap();
+
+		assert_eq!(2, columns);
+		let origins = tiles
+			.iter()
+			.map(|(tile_id, tile)| (*tile_id, (tile.data[0], tile.data[1]), tile.width()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			vec![
+				(0, (1, 1), 2),
+				(1, (5, 1), 2),
+				(2, (1, 5), 2),
+				(3, (5, 5), 2)
+			],
+			origins
+		);
+		// The last pixel of the last tile sits right before the padding
+		assert_eq!(&[6, 6, 0, 255], &tiles[3].1.data[12..16]);
+	}
+}
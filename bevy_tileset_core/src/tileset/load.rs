@@ -1,9 +1,13 @@
+use std::collections::HashMap;
+
 use bevy::{
 	asset::{Asset, AssetPath, AssetServer, Handle},
 	prelude::{Image, Res},
 };
 use bevy_tileset_tiles::prelude::*;
 
+use crate::prelude::TilesetError;
+
 pub trait TextureLoader {
 	fn load_texture<'a, T: Asset, P: Into<AssetPath<'a>>>(&mut self, path: P) -> Handle<Image>;
 }
@@ -20,16 +24,29 @@ impl<'w> TextureLoader for Res<'w, AssetServer> {
 	}
 }
 
+/// Resolves texture paths to already-loaded handles, keyed by the paths used in the tile definitions
+///
+/// Paths without an entry resolve to the default handle.
+impl TextureLoader for HashMap<String, Handle<Image>> {
+	fn load_texture<'a, T: Asset, P: Into<AssetPath<'a>>>(&mut self, path: P) -> Handle<Image> {
+		let path: AssetPath = path.into();
+		self.get(&path.to_string()).cloned().unwrap_or_default()
+	}
+}
+
 /// Load the intermediate tile handles from a set of tile definitions
 ///
 /// This can then be used to generate a new [`RawTileset`](crate::tileset::RawTileset)
 ///
+/// Auto tile rule sets must already be resolved into `Auto` tiles (which the tileset loaders
+/// do), otherwise [`TilesetError::UnresolvedRuleset`] is returned.
+///
 /// # Arguments
 ///
 /// * `tiles`: The collection of tile definitions (in their intended order)
 /// * `asset_loader`: The `AssetServer` or other loader for loading the textures
 ///
-/// returns: Vec<TileHandle, Global>
+/// returns: Result<Vec<TileHandle, Global>, TilesetError>
 ///
 /// # Examples
 ///
@@ -38,8 +55,11 @@ impl<'w> TextureLoader for Res<'w, AssetServer> {
 /// # use bevy_tileset_tiles::prelude::*;
 /// # use bevy::prelude::*;
 ///
-/// fn create_handles(tiles: Vec<TileDef>, asset_server: &AssetServer) -> Vec<TileHandle> {
-/// 	load_tile_handles(tiles, asset_server)
+/// fn create_handles(
+/// 	tiles: Vec<TileDef>,
+/// 	asset_server: &AssetServer,
+/// ) -> Result<Vec<TileHandle>, TilesetError> {
+/// 	load_tile_handles(tiles, &mut asset_server.clone())
 /// }
 ///
 /// fn is_ready(tiles: &Vec<TileHandle>, asset_server: &AssetServer) -> bool {
@@ -49,35 +69,47 @@ impl<'w> TextureLoader for Res<'w, AssetServer> {
 pub fn load_tile_handles<TTiles: IntoIterator<Item = TileDef>, TLoader: TextureLoader>(
 	tiles: TTiles,
 	asset_loader: &mut TLoader,
-) -> Vec<TileHandle> {
+) -> Result<Vec<TileHandle>, TilesetError> {
 	tiles
 		.into_iter()
-		.map(|tile_def| TileHandle {
-			name: tile_def.name.clone(),
-			tile: match tile_def.tile {
-				TileDefType::Standard(path) => {
-					TileHandleType::Standard(asset_loader.load_texture::<Image, String>(path))
+		.map(|tile_def| {
+			Ok(TileHandle {
+				name: tile_def.name.clone(),
+				tile: match tile_def.tile {
+					TileDefType::Standard(path) => {
+						TileHandleType::Standard(asset_loader.load_texture::<Image, String>(path))
+					},
+					TileDefType::Animated(anim) => {
+						TileHandleType::Animated(load_animated(anim.clone(), asset_loader))
+					},
+					#[cfg(feature = "variants")]
+					TileDefType::Variant(variants) => TileHandleType::Variant(
+						variants
+							.iter()
+							.map(|variant| load_variant(variant, asset_loader))
+							.collect(),
+					),
+					#[cfg(feature = "auto-tile")]
+					TileDefType::Auto(autos) => TileHandleType::Auto(
+						autos
+							.iter()
+							.map(|auto| load_auto(auto, asset_loader))
+							.collect(),
+					),
+					// Rule sets are resolved into `Auto` tiles by the loaders
+					#[cfg(feature = "auto-tile")]
+					TileDefType::AutoRuleset(..) => {
+						return Err(TilesetError::UnresolvedRuleset(tile_def.name));
+					},
+					TileDefType::Reference(reference) => TileHandleType::Reference(reference),
 				},
-				TileDefType::Animated(anim) => {
-					TileHandleType::Animated(load_animated(anim.clone(), asset_loader))
-				},
-				#[cfg(feature = "variants")]
-				TileDefType::Variant(variants) => TileHandleType::Variant(
-					variants
-						.iter()
-						.map(|variant| load_variant(variant, asset_loader))
-						.collect(),
-				),
-				#[cfg(feature = "auto-tile")]
-				TileDefType::Auto(autos) => TileHandleType::Auto(
-					autos
-						.iter()
-						.map(|auto| load_auto(auto, asset_loader))
-						.collect(),
-				),
-			},
+				metadata: tile_def.metadata,
+				tags: tile_def.tags,
+				group: tile_def.group,
+				collision: tile_def.collision,
+			})
 		})
-		.collect::<Vec<_>>()
+		.collect()
 }
 
 fn load_animated<TLoader: TextureLoader>(
@@ -89,8 +121,11 @@ fn load_animated<TLoader: TextureLoader>(
 		frames: def
 			.frames
 			.iter()
-			.map(|frame| asset_loader.load_texture::<Image, String>(frame.to_string()))
+			.map(|frame| asset_loader.load_texture::<Image, String>(frame.path().to_string()))
 			.collect(),
+		durations: def.frames.iter().map(|frame| frame.duration()).collect(),
+		mode: def.mode,
+		random_phase: def.random_phase,
 	}
 }
 
@@ -118,7 +153,9 @@ fn load_auto<TLoader: TextureLoader>(
 	asset_loader: &mut TLoader,
 ) -> AutoTileHandle {
 	AutoTileHandle {
-		rule: def.rule,
+		rule: def.rule(),
+		fallback: def.fallback,
+		corners: def.corners,
 		variants: def
 			.variants
 			.iter()
@@ -126,3 +163,24 @@ fn load_auto<TLoader: TextureLoader>(
 			.collect(),
 	}
 }
+
+#[cfg(all(test, feature = "auto-tile"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_reject_unresolved_rulesets() {
+		let mut tile_def = TileDef::new_standard("Wall", "wall.png");
+		tile_def.tile = TileDefType::AutoRuleset(AutoTileRulesetDef {
+			ruleset: String::from("walls.ruleset.ron"),
+			tiles: Default::default(),
+			fallback: None,
+		});
+
+		let mut loader = HashMap::new();
+		assert!(matches!(
+			load_tile_handles([tile_def], &mut loader),
+			Err(TilesetError::UnresolvedRuleset(name)) if name == "Wall"
+		));
+	}
+}
@@ -1,34 +1,66 @@
 //! Types for generating and managing tilesets
 
 use std::collections::HashMap;
+#[cfg(all(feature = "auto-tile", debug_assertions))]
+use std::{collections::HashSet, sync::Mutex};
 
 use bevy::{
 	asset::Asset,
 	prelude::{Component, Handle, Image, TextureAtlas, Vec2},
-	reflect::{TypePath, TypeUuid},
+	reflect::{Reflect, TypeUuid},
 };
 
-pub(crate) use asset::TilesetAssetLoader;
-pub use asset::TilesetDef;
-pub use builder::TilesetBuilder;
+#[cfg(feature = "zip")]
+pub(crate) use archive::ZipTilesetLoader;
+#[cfg(feature = "aseprite")]
+pub use aseprite::AsepriteLoaderSettings;
+#[cfg(feature = "aseprite")]
+pub(crate) use aseprite::AsepriteTilesetLoader;
+pub use asset::{ColorSpace, DuplicateNamePolicy, TileOrder, TilesetDef, TilesetDefaults};
+pub(crate) use asset::{TileDefAssetLoader, TileIndexHistory, TilesetAssetLoader};
+pub use atlas::TileTrim;
+pub use builder::{TilesetBuilder, TilesetOptions};
 pub use error::TilesetError;
 pub use impls::*;
-pub use load::load_tile_handles;
+pub use layout::TilesetLayout;
+#[cfg(feature = "ldtk")]
+pub use ldtk::LdtkLoaderSettings;
+#[cfg(feature = "ldtk")]
+pub(crate) use ldtk::LdtkTilesetLoader;
+pub use load::{load_tile_handles, TextureLoader};
 pub(crate) use param::TilesetMap;
-pub use param::Tilesets;
+pub use param::{TileLookup, TilesetLoadState, Tilesets, TilesetsMut};
 pub use tile_index::TileIndex;
+#[cfg(feature = "tiled")]
+pub use tsx::TsxLoaderSettings;
+#[cfg(feature = "tiled")]
+pub(crate) use tsx::TsxTilesetLoader;
 
 use crate::prelude::*;
 use bevy_tileset_tiles::prelude::*;
 
+#[cfg(feature = "zip")]
+mod archive;
+#[cfg(feature = "aseprite")]
+mod aseprite;
 mod asset;
+mod atlas;
+#[cfg(feature = "fs_loader")]
+mod blocking;
 mod builder;
 pub mod error;
+mod export;
+#[cfg(test)]
+pub(crate) mod fixtures;
 mod impls;
+mod layout;
+#[cfg(feature = "ldtk")]
+mod ldtk;
 mod load;
 mod param;
 mod raw;
 mod tile_index;
+mod tsx;
 
 macro_rules! define_tileset {
 	($(#[$attr:meta])* $vis: vis $name: ident { $($(#[$field_attr:meta])* $field: ident : $type: ty),* $(,)? }) => {
@@ -52,6 +84,18 @@ macro_rules! define_tileset {
 			tile_handles: HashMap<usize, Handle<Image>>,
 			/// The tile IDs mapped by their index in the atlas
 			tile_indices: HashMap<usize, TileId>,
+			/// The explicit durations of animation frames (in seconds) mapped by their index in the atlas
+			frame_durations: HashMap<usize, f32>,
+			/// The borders trimmed from tile textures mapped by their index in the atlas
+			trims: HashMap<usize, TileTrim>,
+			/// The ratio of pixels covered by tiles to the total pixels of the atlas texture
+			atlas_efficiency: f32,
+			/// The definitions of tiles added from a [`TileDef`] mapped by their name
+			tile_defs: HashMap<String, TileDef>,
+			/// Whether tile names are matched regardless of their casing
+			case_insensitive: bool,
+			/// The options this tileset was built with
+			options: TilesetOptions,
 			$(
 				$(#[$field_attr])*
 				$field : $type
@@ -69,18 +113,25 @@ define_tileset!(
 	pub RawTileset {
 		/// The atlas for all registered tiles
 		atlas: TextureAtlas,
+		/// The auto tiles and neighbors that have already been warned about not matching any rule
+		#[cfg(all(feature = "auto-tile", debug_assertions))]
+		unmatched_warnings: Mutex<HashSet<(String, NeighborMask)>>,
 	}
 );
 
 define_tileset!(
 	/// A structure containing the registered tiles as well as a handle to their generated `TextureAtlas`
-	#[derive(Asset, Debug, TypeUuid, TypePath)]
+	#[derive(Asset, Debug, TypeUuid, Reflect)]
 	#[uuid = "4a176882-d7b2-429d-af5c-be418ccc3c52"]
 	pub Tileset {
 		/// A handle to the generated texture atlas
 		atlas: Handle<TextureAtlas>,
 		/// A handle to the generated texture atlas's texture
-		texture: Handle<Image>
+		texture: Handle<Image>,
+		/// The auto tiles and neighbors that have already been warned about not matching any rule
+		#[cfg(all(feature = "auto-tile", debug_assertions))]
+		#[reflect(ignore)]
+		unmatched_warnings: Mutex<HashSet<(String, NeighborMask)>>,
 	}
 );
 
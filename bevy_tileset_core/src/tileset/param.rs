@@ -1,26 +1,138 @@
-use crate::prelude::{Tileset, TilesetId};
-use bevy::asset::{Assets, Handle};
+use crate::prelude::{TileIndex, Tileset, TilesetError, TilesetId, TilesetParent};
+use bevy::asset::{AssetId, AssetServer, Assets, Handle, LoadState};
 use bevy::ecs::system::SystemParam;
-use bevy::prelude::{Query, Res, Resource};
-use std::collections::HashMap;
+use bevy::log::warn;
+use bevy::prelude::{Query, Res, ResMut, Resource, SpriteSheetBundle, TextureAtlas};
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
+/// A system parameter for accessing loaded tilesets by name or ID
+///
+/// Tilesets are registered as soon as they are added to `Assets<Tileset>` and are
+/// deregistered once removed from it (either manually or by dropping all strong handles).
 #[derive(SystemParam)]
 pub struct Tilesets<'w, 's> {
 	tileset_map: Res<'w, TilesetMap>,
 	tilesets: Res<'w, Assets<Tileset>>,
+	asset_server: Option<Res<'w, AssetServer>>,
 
 	/// This field only exists so we can add the `'s` lifetime without Rust freaking out
 	#[allow(dead_code)]
 	phantom_query: Query<'w, 's, ()>,
 }
 
+/// A system parameter for modifying loaded tilesets
+#[derive(SystemParam)]
+pub struct TilesetsMut<'w, 's> {
+	tileset_map: ResMut<'w, TilesetMap>,
+	tilesets: ResMut<'w, Assets<Tileset>>,
+	/// The tileset of every tile entity, used to find which tilesets are still referenced
+	parents: Query<'w, 's, &'static TilesetParent>,
+}
+
+/// A system parameter for looking up tiles by the name of their tileset and their own name
+///
+/// This saves systems that spawn tiles from having to chain tileset and tile lookups themselves.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+/// fn spawn_grass(mut commands: Commands, lookup: TileLookup) {
+/// 	if let Some(bundle) = lookup.sprite_bundle("My Tileset", "Grass") {
+/// 		commands.spawn(bundle);
+/// 	}
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct TileLookup<'w, 's> {
+	tilesets: Tilesets<'w, 's>,
+	atlases: Res<'w, Assets<TextureAtlas>>,
+}
+
+impl<'w, 's> TileLookup<'w, 's> {
+	/// Get a tileset by its name
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The name of the tileset
+	///
+	/// returns: Option<&Tileset>
+	pub fn tileset(&self, tileset: &str) -> Option<&Tileset> { self.tilesets.get_by_name(tileset) }
+
+	/// Get the `TextureAtlas` of a tileset
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The name of the tileset
+	///
+	/// returns: Option<&TextureAtlas>
+	pub fn atlas(&self, tileset: &str) -> Option<&TextureAtlas> {
+		self.atlases.get(self.tileset(tileset)?.atlas())
+	}
+
+	/// Get the index of a tile
+	///
+	/// For variant tiles, a variant is chosen at random.
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The name of the tileset
+	/// * `tile`: The name of the tile
+	///
+	/// returns: Option<TileIndex>
+	pub fn index(&self, tileset: &str, tile: &str) -> Option<TileIndex> {
+		self.tileset(tileset)?.get_tile_index(tile)
+	}
+
+	/// Create a `SpriteSheetBundle` displaying a tile
+	///
+	/// Animated tiles display their first frame. The sprite is created the same way as by
+	/// [`Tileset::tile_sprite`], so reference flips and trim anchors are applied.
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The name of the tileset
+	/// * `tile`: The name of the tile
+	///
+	/// returns: Option<SpriteSheetBundle>
+	pub fn sprite_bundle(&self, tileset: &str, tile: &str) -> Option<SpriteSheetBundle> {
+		let tileset = self.tileset(tileset)?;
+		let index = tileset.get_tile_index(tile)?;
+		Some(SpriteSheetBundle {
+			sprite: tileset.tile_sprite_at(tile, *index.base_index())?,
+			texture_atlas: tileset.atlas().clone(),
+			..Default::default()
+		})
+	}
+}
+
 #[derive(Resource, Default)]
 pub struct TilesetMap {
 	name_to_id: HashMap<String, TilesetId>,
-	id_to_handle: HashMap<TilesetId, Handle<Tileset>>,
-	handle_to_id: HashMap<Handle<Tileset>, TilesetId>,
+	/// The tileset IDs mapped by their lowercase name (only used when case-insensitive)
+	lowercase_to_id: HashMap<String, TilesetId>,
+	id_to_asset: HashMap<TilesetId, AssetId<Tileset>>,
+	asset_to_id: HashMap<AssetId<Tileset>, TilesetId>,
 	id_to_name: HashMap<TilesetId, String>,
+	asset_generations: HashMap<AssetId<Tileset>, u32>,
+	/// The assets of tilesets being tracked with [`TilesetsMut::track`] mapped by their name
+	tracked: HashMap<String, AssetId<Tileset>>,
+	case_insensitive: bool,
+}
+
+/// The load state of a tileset (see [`Tilesets::load_state`])
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TilesetLoadState {
+	/// The tileset is neither loaded nor being tracked
+	NotStarted,
+	/// The tileset is being loaded (or was loaded but hasn't been registered yet)
+	Loading,
+	/// The tileset is loaded and can be accessed by its name
+	Loaded,
+	/// The tileset failed to load
+	Failed,
 }
 
 impl<'w, 's> Deref for Tilesets<'w, 's> {
@@ -40,8 +152,8 @@ impl<'w, 's> Tilesets<'w, 's> {
 	///
 	/// returns: Option<&Tileset>
 	pub fn get_by_id(&self, id: &TilesetId) -> Option<&Tileset> {
-		let handle = self.tileset_map.id_to_handle.get(id)?;
-		self.get(handle)
+		let asset_id = self.tileset_map.id_to_asset.get(id)?;
+		self.get(*asset_id)
 	}
 
 	/// Get a tileset by its name
@@ -52,7 +164,7 @@ impl<'w, 's> Tilesets<'w, 's> {
 	///
 	/// returns: Option<&Tileset>
 	pub fn get_by_name(&self, name: &str) -> Option<&Tileset> {
-		let id = self.tileset_map.name_to_id.get(name)?;
+		let id = self.tileset_map.find_id(name)?;
 		self.get_by_id(id)
 	}
 
@@ -64,9 +176,9 @@ impl<'w, 's> Tilesets<'w, 's> {
 	///
 	/// returns: bool
 	pub fn contains_id(&self, id: &TilesetId) -> bool {
-		if let Some(handle) = self.tileset_map.id_to_handle.get(id) {
+		if let Some(asset_id) = self.tileset_map.id_to_asset.get(id) {
 			// Check underlying asset to ensure the correct response is given
-			self.contains(handle)
+			self.contains(*asset_id)
 		} else {
 			false
 		}
@@ -80,46 +192,532 @@ impl<'w, 's> Tilesets<'w, 's> {
 	///
 	/// returns: bool
 	pub fn contains_name(&self, name: &str) -> bool {
-		if let Some(id) = self.tileset_map.name_to_id.get(name) {
+		if let Some(id) = self.tileset_map.find_id(name) {
 			// Check underlying asset to ensure the correct response is given
 			self.contains_id(id)
 		} else {
 			false
 		}
 	}
+
+	/// Get the load state of a tileset by its name
+	///
+	/// A tileset is `Loaded` once it's registered under the given name. Otherwise, the name must
+	/// have been tracked with [`TilesetsMut::track`] to report whether the tileset is still
+	/// `Loading` or `Failed`, since a tileset's name isn't known until it's loaded.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tileset
+	///
+	/// returns: TilesetLoadState
+	pub fn load_state(&self, name: &str) -> TilesetLoadState {
+		if self.contains_name(name) {
+			return TilesetLoadState::Loaded;
+		}
+		let Some(asset_id) = self.tileset_map.tracked.get(name) else {
+			return TilesetLoadState::NotStarted;
+		};
+		if self.tileset_map.asset_to_id.contains_key(asset_id) {
+			// The tileset was loaded under a different name than the one it was tracked by
+			return TilesetLoadState::Loaded;
+		}
+		let load_state = self
+			.asset_server
+			.as_ref()
+			.and_then(|asset_server| asset_server.get_load_state(*asset_id));
+		match load_state {
+			Some(LoadState::Failed) => TilesetLoadState::Failed,
+			Some(LoadState::NotLoaded) | None => TilesetLoadState::NotStarted,
+			Some(LoadState::Loading | LoadState::Loaded) => TilesetLoadState::Loading,
+		}
+	}
+
+	/// Get the generation of a tileset by its ID
+	///
+	/// The generation starts at `0` and is incremented every time the tileset is reloaded
+	/// (e.g. when one of its files changes on disk while Bevy's `file_watcher` is enabled).
+	/// This can be used to detect when data derived from a tileset needs to be refreshed.
+	///
+	/// # Arguments
+	///
+	/// * `id`: The tileset ID
+	///
+	/// returns: Option<u32>
+	pub fn generation(&self, id: &TilesetId) -> Option<u32> {
+		let asset_id = self.tileset_map.id_to_asset.get(id)?;
+		self.tileset_map.asset_generations.get(asset_id).copied()
+	}
+
+	/// Checks whether tilesets are looked up by name regardless of their casing
+	///
+	/// This is configured by
+	/// [`TilesetPlugin::case_insensitive`](crate::prelude::TilesetPlugin::case_insensitive).
+	///
+	/// returns: bool
+	pub fn is_case_insensitive(&self) -> bool { self.tileset_map.case_insensitive }
+
+	/// The number of registered tilesets
+	///
+	/// returns: usize
+	pub fn len(&self) -> usize { self.tileset_map.id_to_asset.len() }
+
+	/// Checks if no tilesets are registered
+	///
+	/// returns: bool
+	pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+	/// Iterate over all loaded tilesets along with their names
+	///
+	/// The iteration order is unspecified.
+	///
+	/// returns: impl Iterator<Item=(&str, &Tileset)>
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &Tileset)> + '_ {
+		self.tileset_map
+			.name_to_id
+			.iter()
+			.filter_map(|(name, id)| Some((name.as_str(), self.get_by_id(id)?)))
+	}
+
+	/// Iterate over the names of all loaded tilesets
+	///
+	/// The iteration order is unspecified.
+	///
+	/// returns: impl Iterator<Item=&str>
+	pub fn names(&self) -> impl Iterator<Item = &str> + '_ { self.iter().map(|(name, ..)| name) }
+}
+
+impl<'w, 's> TilesetsMut<'w, 's> {
+	/// Track the load state of a tileset by the name it's expected to have
+	///
+	/// This allows [`Tilesets::load_state`] to report whether a tileset is still loading (or
+	/// failed to load) before it's registered under its name. Tracking a name again replaces the
+	/// previously tracked tileset.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name the tileset is expected to have
+	/// * `handle`: The handle of the tileset being loaded
+	///
+	/// returns: ()
+	pub fn track(&mut self, name: &str, handle: &Handle<Tileset>) {
+		self.tileset_map
+			.tracked
+			.insert(name.to_string(), handle.id());
+	}
+
+	/// Remove a tileset by its name
+	///
+	/// The tileset is removed from `Assets<Tileset>` and is no longer accessible by its name
+	/// or ID. Any handles to it become invalid.
+	///
+	/// # Arguments
+	///
+	/// * `name`: The name of the tileset
+	///
+	/// returns: Option<Tileset>
+	pub fn remove(&mut self, name: &str) -> Option<Tileset> {
+		let id = *self.tileset_map.find_id(name)?;
+		self.remove_by_id(&id)
+	}
+
+	/// Remove a tileset by its ID
+	///
+	/// The tileset is removed from `Assets<Tileset>` and is no longer accessible by its name
+	/// or ID. Any handles to it become invalid.
+	///
+	/// # Arguments
+	///
+	/// * `id`: The tileset ID
+	///
+	/// returns: Option<Tileset>
+	pub fn remove_by_id(&mut self, id: &TilesetId) -> Option<Tileset> {
+		let asset_id = *self.tileset_map.id_to_asset.get(id)?;
+		self.tileset_map.deregister_tileset(asset_id);
+		self.tilesets.remove(asset_id)
+	}
+
+	/// Remove every tileset that isn't referenced by any entity's [`TilesetParent`]
+	///
+	/// Removing a tileset drops its handles to its texture atlas and atlas texture, so they are
+	/// unloaded as well once nothing else holds a strong handle to them. Note that a tileset is
+	/// already unloaded automatically once all strong handles to it are dropped; this is for
+	/// unloading tilesets that are still held (e.g. by a level's asset collection) but no longer
+	/// have any tiles spawned from them.
+	///
+	/// Tilesets that are loaded but haven't had any tiles spawned yet are removed too, so this
+	/// should only be called once all tiles for the current level have been spawned.
+	///
+	/// Returns the IDs of the removed tilesets.
+	///
+	/// returns: Vec<TilesetId>
+	pub fn gc(&mut self) -> Vec<TilesetId> {
+		let referenced = self
+			.parents
+			.iter()
+			.map(|parent| parent.0)
+			.collect::<HashSet<_>>();
+		let unreferenced = self
+			.tileset_map
+			.id_to_asset
+			.iter()
+			.filter(|(id, ..)| !referenced.contains(id))
+			.map(|(id, asset_id)| (*id, *asset_id))
+			.collect::<Vec<_>>();
+
+		for (.., asset_id) in unreferenced.iter() {
+			self.tilesets.remove(*asset_id);
+			self.tileset_map.deregister_tileset(*asset_id);
+		}
+		unreferenced.into_iter().map(|(id, ..)| id).collect()
+	}
+
+	/// Rename a loaded tileset
+	///
+	/// The tileset's ID remains unchanged, so any ID-based references to it stay valid. Only the
+	/// name it's looked up by is changed: the asset itself isn't modified, so no
+	/// `AssetEvent::Modified` is sent and [`Tileset::name`] still returns its original name.
+	///
+	/// # Arguments
+	///
+	/// * `old`: The current name of the tileset
+	/// * `new`: The new name of the tileset
+	///
+	/// returns: Result<(), TilesetError>
+	pub fn rename(&mut self, old: &str, new: &str) -> Result<(), TilesetError> {
+		let id = *self
+			.tileset_map
+			.find_id(old)
+			.ok_or_else(|| TilesetError::TilesetNotFound(old.to_string()))?;
+		// Allow changing only the casing of a tileset's name, even when case-insensitive
+		if self.tileset_map.name_to_id.contains_key(new)
+			|| matches!(self.tileset_map.find_id(new), Some(other) if *other != id)
+		{
+			return Err(TilesetError::TilesetAlreadyExists(new.to_string()));
+		}
+		if let Some(old) = self.tileset_map.id_to_name.insert(id, new.to_string()) {
+			self.tileset_map.remove_name(&old);
+		}
+		self.tileset_map.insert_name(new.to_string(), id);
+		Ok(())
+	}
 }
 
 impl TilesetMap {
+	/// Creates an empty map
+	///
+	/// # Arguments
+	///
+	/// * `case_insensitive`: Whether tilesets are looked up by name regardless of their casing
+	///
+	/// returns: TilesetMap
+	pub(crate) fn new(case_insensitive: bool) -> Self {
+		Self {
+			case_insensitive,
+			..Default::default()
+		}
+	}
+
+	/// Whether tilesets (and the tiles within them) are looked up regardless of their casing
+	pub(crate) fn is_case_insensitive(&self) -> bool { self.case_insensitive }
+
+	/// Find the ID of a tileset by its name, falling back to a case-insensitive match if enabled
+	fn find_id(&self, name: &str) -> Option<&TilesetId> {
+		self.name_to_id.get(name).or_else(|| {
+			if !self.case_insensitive {
+				return None;
+			}
+			self.lowercase_to_id.get(&name.to_lowercase())
+		})
+	}
+
+	/// Map a name to a tileset ID
+	fn insert_name(&mut self, name: String, id: TilesetId) {
+		if self.case_insensitive {
+			self.lowercase_to_id.insert(name.to_lowercase(), id);
+		}
+		self.name_to_id.insert(name, id);
+	}
+
+	/// Remove the mapping of a name to a tileset ID
+	fn remove_name(&mut self, name: &str) {
+		let Some(id) = self.name_to_id.remove(name) else {
+			return;
+		};
+		let lowercase = name.to_lowercase();
+		if self.lowercase_to_id.get(&lowercase) != Some(&id) {
+			return;
+		}
+		// Another tileset may differ from the removed one only by its casing
+		match self
+			.name_to_id
+			.iter()
+			.find(|(other, ..)| other.to_lowercase() == lowercase)
+		{
+			Some((.., other)) => self.lowercase_to_id.insert(lowercase, *other),
+			None => self.lowercase_to_id.remove(&lowercase),
+		};
+	}
+
 	/// Register a tileset for easy lookup in the [Tilesets] system param.
 	///
 	/// # Arguments
 	///
 	/// * `tileset`: The tileset to register
-	/// * `handle`: The handle to the tileset
+	/// * `asset_id`: The asset ID of the tileset
 	///
 	/// returns: ()
-	pub(crate) fn register_tileset(&mut self, tileset: &Tileset, handle: &Handle<Tileset>) {
-		self.handle_to_id.insert(handle.clone_weak(), *tileset.id());
+	pub(crate) fn register_tileset(&mut self, tileset: &Tileset, asset_id: AssetId<Tileset>) {
+		if let Some(other) = self.id_to_asset.get(tileset.id()) {
+			if *other != asset_id {
+				// Overwriting the other tileset would leave both with broken lookups
+				warn!(
+					"tileset {:?} was not registered: its ID {} is already used by {:?}",
+					tileset.name(),
+					tileset.id(),
+					self.id_to_name.get(tileset.id())
+				);
+				return;
+			}
+		}
+		self.asset_to_id.insert(asset_id, *tileset.id());
 		self.id_to_name
 			.insert(*tileset.id(), tileset.name().to_string());
-		self.name_to_id
-			.insert(tileset.name().to_string(), *tileset.id());
-		self.id_to_handle.insert(*tileset.id(), handle.clone_weak());
+		self.insert_name(tileset.name().to_string(), *tileset.id());
+		self.id_to_asset.insert(*tileset.id(), asset_id);
+		self.asset_generations.entry(asset_id).or_insert(0);
+	}
+
+	/// Re-registers a reloaded tileset, incrementing its generation
+	///
+	/// # Arguments
+	///
+	/// * `tileset`: The reloaded tileset
+	/// * `asset_id`: The asset ID of the tileset
+	///
+	/// returns: ()
+	pub(crate) fn reregister_tileset(&mut self, tileset: &Tileset, asset_id: AssetId<Tileset>) {
+		let generation = self.asset_generations.get(&asset_id).copied();
+		// The reloaded tileset may have a new name or ID, so drop the old entries first
+		self.deregister_tileset(asset_id);
+		self.register_tileset(tileset, asset_id);
+		self.asset_generations
+			.insert(asset_id, generation.map_or(0, |generation| generation + 1));
 	}
 
 	/// Deregisters a tileset so it is no longer tracked
 	///
 	/// # Arguments
 	///
-	/// * `handle`: The handle to the tileset
+	/// * `asset_id`: The asset ID of the tileset
 	///
 	/// returns: ()
-	pub(crate) fn deregister_tileset(&mut self, handle: &Handle<Tileset>) {
-		if let Some(ref id) = self.handle_to_id.remove(handle) {
+	pub(crate) fn deregister_tileset(&mut self, asset_id: AssetId<Tileset>) {
+		if let Some(ref id) = self.asset_to_id.remove(&asset_id) {
 			if let Some(ref name) = self.id_to_name.remove(id) {
-				self.name_to_id.remove(name);
+				self.remove_name(name);
 			}
-			self.id_to_handle.remove(id);
+			self.id_to_asset.remove(id);
 		}
+		self.asset_generations.remove(&asset_id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use bevy::{
+		asset::{AssetServer, Assets, LoadState},
+		ecs::system::SystemState,
+		prelude::Handle,
+	};
+
+	use super::{TilesetLoadState, TilesetMap, Tilesets, TilesetsMut};
+	use crate::{
+		prelude::{Tileset, TilesetError, TilesetId, TilesetParent},
+		tileset::fixtures::{add_tileset, asset_app, wait_for_load, TempDir, FIXTURES},
+	};
+
+	#[test]
+	fn should_remove_tilesets() {
+		let mut app = asset_app(FIXTURES);
+		let _handles = [
+			add_tileset(&mut app, "Terrain", TilesetId(0)),
+			add_tileset(&mut app, "Walls", TilesetId(1)),
+		];
+
+		let mut state = SystemState::<TilesetsMut>::new(&mut app.world);
+		let mut tilesets = state.get_mut(&mut app.world);
+		assert_eq!(
+			Some("Terrain"),
+			tilesets.remove("Terrain").as_ref().map(Tileset::name)
+		);
+		assert_eq!(
+			Some("Walls"),
+			tilesets
+				.remove_by_id(&TilesetId(1))
+				.as_ref()
+				.map(Tileset::name)
+		);
+		assert!(tilesets.remove("Terrain").is_none());
+		assert!(tilesets.remove_by_id(&TilesetId(1)).is_none());
+		state.apply(&mut app.world);
+
+		let mut state = SystemState::<Tilesets>::new(&mut app.world);
+		let tilesets = state.get(&app.world);
+		assert!(tilesets.is_empty());
+		assert!(!tilesets.contains_name("Terrain"));
+		assert!(!tilesets.contains_id(&TilesetId(1)));
+		assert!(app.world.resource::<Assets<Tileset>>().is_empty());
+	}
+
+	#[test]
+	fn should_rename_tilesets() {
+		let mut app = asset_app(FIXTURES);
+		let _handles = [
+			add_tileset(&mut app, "Terrain", TilesetId(0)),
+			add_tileset(&mut app, "Walls", TilesetId(1)),
+		];
+
+		let mut state = SystemState::<TilesetsMut>::new(&mut app.world);
+		let mut tilesets = state.get_mut(&mut app.world);
+		assert!(matches!(
+			tilesets.rename("Terrain", "Walls"),
+			Err(TilesetError::TilesetAlreadyExists(..))
+		));
+		assert!(matches!(
+			tilesets.rename("Missing", "Ground"),
+			Err(TilesetError::TilesetNotFound(..))
+		));
+		assert!(tilesets.rename("Terrain", "Ground").is_ok());
+		state.apply(&mut app.world);
+		app.update();
+		app.update();
+
+		let mut state = SystemState::<Tilesets>::new(&mut app.world);
+		let tilesets = state.get(&app.world);
+		assert_eq!(
+			Some(&TilesetId(0)),
+			tilesets.get_by_name("Ground").map(Tileset::id)
+		);
+		assert!(!tilesets.contains_name("Terrain"));
+		// Only the lookup changes, so the asset isn't modified and reloaded
+		assert_eq!(Some(0), tilesets.generation(&TilesetId(0)));
+		assert_eq!(
+			Some("Terrain"),
+			tilesets.get_by_id(&TilesetId(0)).map(Tileset::name)
+		);
+	}
+
+	#[test]
+	fn should_rename_case_insensitive_tilesets() {
+		let mut app = asset_app(FIXTURES);
+		app.insert_resource(TilesetMap::new(true));
+		let _handles = [
+			add_tileset(&mut app, "Terrain", TilesetId(0)),
+			add_tileset(&mut app, "Walls", TilesetId(1)),
+		];
+
+		let mut state = SystemState::<TilesetsMut>::new(&mut app.world);
+		let mut tilesets = state.get_mut(&mut app.world);
+		assert!(matches!(
+			tilesets.rename("walls", "terrain"),
+			Err(TilesetError::TilesetAlreadyExists(..))
+		));
+		assert!(tilesets.rename("terrain", "TERRAIN").is_ok());
+		state.apply(&mut app.world);
+
+		let mut state = SystemState::<Tilesets>::new(&mut app.world);
+		let tilesets = state.get(&app.world);
+		assert_eq!(
+			Some(&TilesetId(0)),
+			tilesets.get_by_name("terrain").map(Tileset::id)
+		);
+		assert_eq!(
+			Some(&TilesetId(1)),
+			tilesets.get_by_name("WALLS").map(Tileset::id)
+		);
+		assert_eq!(vec!["TERRAIN", "Walls"], {
+			let mut names = tilesets.names().collect::<Vec<_>>();
+			names.sort();
+			names
+		});
+	}
+
+	#[test]
+	fn should_collect_unreferenced_tilesets() {
+		let mut app = asset_app(FIXTURES);
+		let _handles = [
+			add_tileset(&mut app, "Terrain", TilesetId(0)),
+			add_tileset(&mut app, "Walls", TilesetId(1)),
+		];
+		app.world.spawn(TilesetParent(TilesetId(1)));
+
+		let mut state = SystemState::<TilesetsMut>::new(&mut app.world);
+		let mut tilesets = state.get_mut(&mut app.world);
+		assert_eq!(vec![TilesetId(0)], tilesets.gc());
+		assert!(tilesets.gc().is_empty());
+		state.apply(&mut app.world);
+
+		let mut state = SystemState::<Tilesets>::new(&mut app.world);
+		let tilesets = state.get(&app.world);
+		assert_eq!(1, tilesets.len());
+		assert_eq!(
+			vec![("Walls", TilesetId(1))],
+			tilesets
+				.iter()
+				.map(|(name, tileset)| (name, *tileset.id()))
+				.collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn should_report_load_states() {
+		let dir = TempDir::new("load_state");
+		image::RgbaImage::new(4, 4)
+			.save(dir.path().join("grass.png"))
+			.unwrap();
+		fs::write(
+			dir.path().join("terrain.ron"),
+			r#"(
+				name: Some("Terrain"),
+				id: 0,
+				tile_defs: { 0: (name: "Grass", tile: Standard("grass.png")) },
+			)"#,
+		)
+		.unwrap();
+		fs::write(dir.path().join("broken.ron"), "not a tileset").unwrap();
+		let mut app = asset_app(dir.path());
+
+		let asset_server = app.world.resource::<AssetServer>();
+		let terrain: Handle<Tileset> = asset_server.load("terrain.ron");
+		let broken: Handle<Tileset> = asset_server.load("broken.ron");
+		let mut state = SystemState::<TilesetsMut>::new(&mut app.world);
+		let mut tilesets = state.get_mut(&mut app.world);
+		tilesets.track("Terrain", &terrain);
+		tilesets.track("Broken", &broken);
+		state.apply(&mut app.world);
+
+		let mut state = SystemState::<Tilesets>::new(&mut app.world);
+		assert_eq!(
+			TilesetLoadState::Loading,
+			state.get(&app.world).load_state("Terrain")
+		);
+		assert_eq!(
+			TilesetLoadState::NotStarted,
+			state.get(&app.world).load_state("Walls")
+		);
+
+		assert_eq!(LoadState::Loaded, wait_for_load(&mut app, &terrain));
+		assert_eq!(
+			TilesetLoadState::Loaded,
+			state.get(&app.world).load_state("Terrain")
+		);
+		assert_eq!(LoadState::Failed, wait_for_load(&mut app, &broken));
+		assert_eq!(
+			TilesetLoadState::Failed,
+			state.get(&app.world).load_state("Broken")
+		);
 	}
 }
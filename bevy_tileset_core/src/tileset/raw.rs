@@ -1,12 +1,44 @@
 use crate::prelude::{RawTileset, Tileset};
-use bevy::prelude::Assets;
+use bevy::prelude::{Assets, Handle};
 use bevy::sprite::TextureAtlas;
 
 impl RawTileset {
 	/// Converts this raw tileset into a finalized tileset asset
 	pub fn into_asset(self, assets: &mut Assets<TextureAtlas>) -> Tileset {
+		let atlas = assets.add(self.atlas.clone());
+		self.into_asset_with_atlas(atlas)
+	}
+
+	/// Converts raw tilesets sharing the same atlas into finalized tileset assets
+	///
+	/// The atlas is only added once, so every tileset refers to the same `Handle<TextureAtlas>`.
+	/// The tilesets are expected to come from
+	/// [`TilesetBuilder::build_shared`](crate::prelude::TilesetBuilder::build_shared).
+	///
+	/// # Arguments
+	///
+	/// * `tilesets`: The raw tilesets sharing an atlas
+	/// * `assets`: The texture atlas assets
+	///
+	/// returns: Vec<Tileset>
+	///
+	pub fn into_shared_assets(
+		tilesets: Vec<RawTileset>,
+		assets: &mut Assets<TextureAtlas>,
+	) -> Vec<Tileset> {
+		let Some(first) = tilesets.first() else {
+			return Vec::new();
+		};
+		let atlas = assets.add(first.atlas.clone());
+		tilesets
+			.into_iter()
+			.map(|tileset| tileset.into_asset_with_atlas(atlas.clone()))
+			.collect()
+	}
+
+	/// Converts this raw tileset into a finalized tileset asset using an already added atlas
+	fn into_asset_with_atlas(self, atlas: Handle<TextureAtlas>) -> Tileset {
 		let texture = self.atlas().texture.clone();
-		let atlas = assets.add(self.atlas);
 
 		Tileset {
 			id: self.id,
@@ -18,8 +50,16 @@ impl RawTileset {
 			tile_names: self.tile_names,
 			tile_handles: self.tile_handles,
 			tile_indices: self.tile_indices,
+			frame_durations: self.frame_durations,
+			trims: self.trims,
+			atlas_efficiency: self.atlas_efficiency,
+			tile_defs: self.tile_defs,
+			case_insensitive: self.case_insensitive,
+			options: self.options,
 			atlas,
 			texture,
+			#[cfg(all(feature = "auto-tile", debug_assertions))]
+			unmatched_warnings: self.unmatched_warnings,
 		}
 	}
 }
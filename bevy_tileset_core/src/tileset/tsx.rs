@@ -0,0 +1,634 @@
+//! Exporting tilesets to (and, with the `tiled` feature, importing them from) the
+//! [Tiled](https://www.mapeditor.org/) `.tsx` format
+
+#[cfg(feature = "tiled")]
+use std::{
+	collections::{BTreeMap, HashMap},
+	path::PathBuf,
+};
+use std::{fmt::Write as _, fs, path::Path};
+
+use bevy::prelude::{Assets, Image, TextureAtlas};
+#[cfg(feature = "tiled")]
+use bevy::{
+	asset::{io::Reader, AssetLoader, AsyncReadExt, BoxedFuture, Handle, LoadContext},
+	prelude::{AssetId, FromWorld, World},
+	render::texture::CompressedImageFormats,
+	utils::Uuid,
+};
+#[cfg(feature = "tiled")]
+use bevy_tileset_tiles::prelude::{AnimatedTileHandle, AnimationMode, TileHandle};
+#[cfg(feature = "tiled")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tiled")]
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::prelude::{Tileset, TilesetError};
+#[cfg(feature = "tiled")]
+use crate::{
+	prelude::{TileGroupId, TilesetBuilder, TilesetId},
+	tileset::asset::{
+		case_insensitive,
+		finalize_tileset,
+		load_image,
+		slice_image,
+		supported_compressed_formats,
+		TilesetTextureStore,
+	},
+};
+
+impl Tileset {
+	/// Exports this tileset as a Tiled `.tsx` file
+	///
+	/// The atlas texture is written as a PNG next to the `.tsx` file (with the same file stem),
+	/// and every atlas index is exported as a Tiled tile carrying its tile name as a `name`
+	/// property. Any padding or extrusion in the atlas is mapped onto Tiled's `spacing` and `margin`.
+	///
+	/// # Arguments
+	///
+	/// * `atlases`: The `TextureAtlas` assets containing this tileset's atlas
+	/// * `textures`: The image assets containing the atlas texture
+	/// * `path`: The path of the `.tsx` file to write
+	///
+	/// returns: Result<(), TilesetError>
+	///
+	pub fn export_tsx<P: AsRef<Path>>(
+		&self,
+		atlases: &Assets<TextureAtlas>,
+		textures: &Assets<Image>,
+		path: P,
+	) -> Result<(), TilesetError> {
+		let path = path.as_ref();
+		let atlas = atlases
+			.get(self.atlas())
+			.ok_or(TilesetError::ImageNotFound)?;
+		let image_path = path.with_extension("png");
+		self.save_atlas_png(textures, &image_path)?;
+
+		let rects = &atlas.textures;
+		let tile_size = self.tile_size();
+		let first = rects.first().map(|rect| rect.min).unwrap_or_default();
+		let columns = rects
+			.iter()
+			.take_while(|rect| rect.min.y == first.y)
+			.count()
+			.max(1);
+		let spacing = rects
+			.get(1)
+			.filter(|_| columns > 1)
+			.map(|rect| rect.min.x - first.x - tile_size.x)
+			.unwrap_or_default();
+
+		let mut tsx = String::new();
+		let _ = writeln!(tsx, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+		let _ = writeln!(
+			tsx,
+			r#"<tileset version="1.10" name="{}" tilewidth="{}" tileheight="{}" spacing="{}" margin="{}" tilecount="{}" columns="{}">"#,
+			escape_xml(self.name()),
+			tile_size.x,
+			tile_size.y,
+			spacing,
+			first.x,
+			rects.len(),
+			columns
+		);
+		let _ = writeln!(tsx, r#" <properties>"#);
+		let _ = writeln!(
+			tsx,
+			r#"  <property name="id" type="int" value="{}"/>"#,
+			self.id()
+		);
+		let _ = writeln!(tsx, r#" </properties>"#);
+		let _ = writeln!(
+			tsx,
+			r#" <image source="{}" width="{}" height="{}"/>"#,
+			escape_xml(
+				&image_path
+					.file_name()
+					.map(|name| name.to_string_lossy())
+					.unwrap_or_default()
+			),
+			atlas.size.x,
+			atlas.size.y
+		);
+		for index in 0..rects.len() {
+			if let Some(name) = self.get_tile_name_by_index(&index) {
+				let _ = writeln!(tsx, r#" <tile id="{}">"#, index);
+				let _ = writeln!(tsx, r#"  <properties>"#);
+				let _ = writeln!(
+					tsx,
+					r#"   <property name="name" value="{}"/>"#,
+					escape_xml(name)
+				);
+				let _ = writeln!(tsx, r#"  </properties>"#);
+				let _ = writeln!(tsx, r#" </tile>"#);
+			}
+		}
+		let _ = writeln!(tsx, "</tileset>");
+
+		fs::write(path, tsx)?;
+		Ok(())
+	}
+}
+
+/// Escapes the characters that are not allowed within XML attribute values
+fn escape_xml(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&apos;")
+}
+
+/// An asset loader for Tiled `.tsx` tilesets
+///
+/// The tileset image is sliced into individual tiles according to the TSX grid. Each tile is
+/// named after its `name` property (or its Tiled tile ID if none is given) and its remaining
+/// properties are stored as the tile's metadata. Tiles with an `<animation>` become animated
+/// tiles using the referenced frames and their durations.
+///
+/// The ID of the tileset is taken from [`TsxLoaderSettings::id`]. If that isn't set, the `id`
+/// property of the tileset is used instead (exported tilesets always have one).
+///
+/// Only accessible with the `tiled` feature enabled
+#[cfg(feature = "tiled")]
+pub(crate) struct TsxTilesetLoader {
+	supported_compressed_formats: CompressedImageFormats,
+	case_insensitive: bool,
+}
+
+/// The settings for loading a Tiled `.tsx` tileset
+///
+/// Tilesets are registered by their ID, so every tileset should be given its own ID:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_tileset_core::prelude::*;
+/// fn load_terrain(asset_server: Res<AssetServer>) {
+/// 	let handle: Handle<Tileset> =
+/// 		asset_server.load_with_settings("terrain.tsx", |settings: &mut TsxLoaderSettings| {
+/// 			settings.id = Some(TilesetId(2));
+/// 		});
+/// }
+/// ```
+///
+/// Only accessible with the `tiled` feature enabled
+#[cfg(feature = "tiled")]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct TsxLoaderSettings {
+	/// The ID of the tileset
+	///
+	/// If `None`, the `id` property of the tileset is used (or the default ID if it has none).
+	pub id: Option<TilesetId>,
+}
+
+/// The parsed contents of a `.tsx` file
+#[cfg(feature = "tiled")]
+#[derive(Default)]
+struct TsxTileset {
+	name: Option<String>,
+	tile_width: u32,
+	tile_height: u32,
+	spacing: u32,
+	margin: u32,
+	tile_count: u32,
+	columns: u32,
+	image: Option<String>,
+	properties: Vec<TsxProperty>,
+	tiles: BTreeMap<u32, TsxTile>,
+}
+
+/// A tile with custom data in a `.tsx` file
+#[cfg(feature = "tiled")]
+#[derive(Default)]
+struct TsxTile {
+	properties: Vec<TsxProperty>,
+	/// The animation frames as pairs of the frame's tile ID and its duration (in milliseconds)
+	animation: Vec<(u32, u32)>,
+}
+
+/// A custom property in a `.tsx` file
+#[cfg(feature = "tiled")]
+struct TsxProperty {
+	name: String,
+	kind: Option<String>,
+	value: String,
+}
+
+#[cfg(feature = "tiled")]
+impl FromWorld for TsxTilesetLoader {
+	fn from_world(world: &mut World) -> Self {
+		Self {
+			supported_compressed_formats: supported_compressed_formats(world),
+			case_insensitive: case_insensitive(world),
+		}
+	}
+}
+
+#[cfg(feature = "tiled")]
+impl AssetLoader for TsxTilesetLoader {
+	type Asset = Tileset;
+	type Settings = TsxLoaderSettings;
+	type Error = TilesetError;
+
+	fn load<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		settings: &'a Self::Settings,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			reader.read_to_end(&mut bytes).await?;
+			let tsx = TsxTileset::parse(&bytes)?;
+
+			// === Load Image === //
+			let source = tsx
+				.image
+				.as_ref()
+				.ok_or_else(|| TilesetError::InvalidTsx("missing <image> element".to_string()))?;
+			let path = match load_context.path().parent() {
+				Some(parent) => parent.join(source),
+				None => PathBuf::from(source),
+			};
+			let (.., image) = load_image(
+				load_context,
+				AssetId::default(),
+				path,
+				self.supported_compressed_formats,
+			)
+			.await?;
+
+			// === Slice Tiles === //
+			let columns = if tsx.columns > 0 {
+				tsx.columns
+			} else {
+				image
+					.width()
+					.checked_add(tsx.spacing)
+					.and_then(|width| width.checked_sub(tsx.margin))
+					.zip(tsx.tile_width.checked_add(tsx.spacing))
+					.map_or(0, |(width, stride)| width / stride)
+			};
+			if columns == 0 && tsx.tile_count > 0 {
+				return Err(TilesetError::InvalidTsx(
+					"the image is too narrow to fit a single tile".to_string(),
+				));
+			}
+			let mut images = HashMap::with_capacity(tsx.tile_count as usize);
+			let mut frames = Vec::with_capacity(tsx.tile_count as usize);
+			for id in 0..tsx.tile_count {
+				let x = tsx.margin + (id % columns) * (tsx.tile_width + tsx.spacing);
+				let y = tsx.margin + (id / columns) * (tsx.tile_height + tsx.spacing);
+				let tile = slice_image(&image, x, y, tsx.tile_width, tsx.tile_height).ok_or_else(
+					|| TilesetError::InvalidTsx(format!("tile {} is outside of the image", id)),
+				)?;
+				let handle: Handle<Image> = load_context.get_label_handle(format!("tile_{}", id));
+				images.insert(handle.clone(), tile);
+				frames.push(handle);
+			}
+
+			// === Build Tiles === //
+			let mut store = TilesetTextureStore {
+				load_context,
+				images,
+			};
+			let mut builder = TilesetBuilder::new(Some(columns as usize));
+			builder
+				.padding(tsx.spacing)
+				.case_insensitive(self.case_insensitive);
+			for (id, handle) in frames.iter().enumerate() {
+				let tile = tsx.tiles.get(&(id as u32));
+				let properties = tile
+					.map(|tile| tile.properties.as_slice())
+					.unwrap_or_default();
+				let name = properties
+					.iter()
+					.find(|property| property.name == "name")
+					.map(|property| property.value.clone())
+					.unwrap_or_else(|| id.to_string());
+
+				let mut tile_handle = match tile.filter(|tile| !tile.animation.is_empty()) {
+					Some(tile) => {
+						let mut animation = AnimatedTileHandle {
+							speed: 1.0,
+							frames: Vec::with_capacity(tile.animation.len()),
+							durations: Vec::with_capacity(tile.animation.len()),
+							mode: AnimationMode::Loop,
+							random_phase: false,
+						};
+						for (frame, duration) in &tile.animation {
+							let frame = frames.get(*frame as usize).ok_or_else(|| {
+								TilesetError::InvalidTsx(format!(
+									"unknown animation frame {}",
+									frame
+								))
+							})?;
+							animation.frames.push(frame.clone());
+							animation.durations.push(Some(*duration as f32 / 1000.0));
+						}
+						TileHandle::new_animated(name, animation)
+					},
+					None => TileHandle::new_standard(name, handle.clone()),
+				};
+				tile_handle.metadata = properties_to_metadata(properties);
+				builder.add_tile(tile_handle, id as TileGroupId, &store)?;
+			}
+
+			// === Create Raw Tileset === //
+			let id = match settings.id {
+				Some(id) => id,
+				None => tsx
+					.properties
+					.iter()
+					.find(|property| property.name == "id")
+					.map(|property| property.value.parse::<TilesetId>())
+					.transpose()
+					.map_err(|_| TilesetError::InvalidTsx("invalid `id` property".to_string()))?
+					.unwrap_or_default(),
+			};
+			let name = tsx
+				.name
+				.unwrap_or_else(|| Uuid::new_v4().hyphenated().to_string());
+			let raw_tileset = builder.build(name, id, &mut store)?;
+
+			Ok(finalize_tileset(raw_tileset, "atlas", load_context))
+		})
+	}
+
+	fn extensions(&self) -> &[&str] { &["tsx"] }
+}
+
+#[cfg(feature = "tiled")]
+impl TsxTileset {
+	/// Parse the XML contents of a `.tsx` file
+	fn parse(bytes: &[u8]) -> Result<Self, TilesetError> {
+		let mut tsx = Self::default();
+		let mut current_tile: Option<(u32, TsxTile)> = None;
+
+		for event in EventReader::new(bytes) {
+			let event = event.map_err(|err| TilesetError::InvalidTsx(err.to_string()))?;
+			match event {
+				XmlEvent::StartElement {
+					name, attributes, ..
+				} => {
+					let attribute = |key: &str| {
+						attributes
+							.iter()
+							.find(|attr| attr.name.local_name == key)
+							.map(|attr| attr.value.clone())
+					};
+					let number = |key: &str| -> Result<u32, TilesetError> {
+						attribute(key)
+							.map(|value| value.parse::<u32>())
+							.transpose()
+							.map(|value| value.unwrap_or_default())
+							.map_err(|_| TilesetError::InvalidTsx(format!("invalid {:?}", key)))
+					};
+
+					match name.local_name.as_str() {
+						"tileset" => {
+							tsx.name = attribute("name");
+							tsx.tile_width = number("tilewidth")?;
+							tsx.tile_height = number("tileheight")?;
+							tsx.spacing = number("spacing")?;
+							tsx.margin = number("margin")?;
+							tsx.tile_count = number("tilecount")?;
+							tsx.columns = number("columns")?;
+						},
+						"image" if current_tile.is_none() => tsx.image = attribute("source"),
+						"tile" => current_tile = Some((number("id")?, TsxTile::default())),
+						"property" => {
+							let property = TsxProperty {
+								name: attribute("name").unwrap_or_default(),
+								kind: attribute("type"),
+								value: attribute("value").unwrap_or_default(),
+							};
+							match current_tile.as_mut() {
+								Some((.., tile)) => tile.properties.push(property),
+								None => tsx.properties.push(property),
+							}
+						},
+						"frame" => {
+							if let Some((.., tile)) = current_tile.as_mut() {
+								tile.animation
+									.push((number("tileid")?, number("duration")?));
+							}
+						},
+						_ => {},
+					}
+				},
+				XmlEvent::EndElement { name } if name.local_name == "tile" => {
+					if let Some((id, tile)) = current_tile.take() {
+						tsx.tiles.insert(id, tile);
+					}
+				},
+				_ => {},
+			}
+		}
+
+		if tsx.tile_width == 0 || tsx.tile_height == 0 {
+			return Err(TilesetError::InvalidTsx(
+				"missing tile dimensions".to_string(),
+			));
+		}
+
+		Ok(tsx)
+	}
+}
+
+/// Convert Tiled properties (other than `name`) into tile metadata
+#[cfg(feature = "tiled")]
+fn properties_to_metadata(properties: &[TsxProperty]) -> Option<ron::Value> {
+	let mut map = ron::Map::new();
+	for property in properties.iter().filter(|property| property.name != "name") {
+		let value = match property.kind.as_deref() {
+			Some("int") => property
+				.value
+				.parse::<i64>()
+				.map(|value| ron::Value::Number(ron::Number::new(value)))
+				.ok(),
+			Some("float") => property
+				.value
+				.parse::<f64>()
+				.map(|value| ron::Value::Number(ron::Number::new(value)))
+				.ok(),
+			Some("bool") => property.value.parse::<bool>().map(ron::Value::Bool).ok(),
+			_ => None,
+		}
+		.unwrap_or_else(|| ron::Value::String(property.value.clone()));
+		map.insert(ron::Value::String(property.name.clone()), value);
+	}
+
+	if map.is_empty() {
+		None
+	} else {
+		Some(ron::Value::Map(map))
+	}
+}
+
+#[cfg(all(test, feature = "tiled"))]
+mod tests {
+	use std::fs;
+
+	use bevy::{
+		asset::{AssetServer, Assets, LoadState},
+		prelude::{App, Handle, Image, TextureAtlas},
+	};
+
+	use super::{properties_to_metadata, TsxLoaderSettings, TsxTileset};
+	use crate::{
+		prelude::{Tileset, TilesetBuilder, TilesetId},
+		tileset::fixtures::{asset_app, solid_image, wait_for_load, TempDir},
+	};
+
+	#[test]
+	fn should_parse_tsx() {
+		let tsx = br#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="World" tilewidth="16" tileheight="16" spacing="2" margin="1" tilecount="4" columns="2">
+ <image source="world.png" width="36" height="36"/>
+ <tile id="1">
+  <properties>
+   <property name="name" value="Water"/>
+   <property name="depth" type="int" value="3"/>
+  </properties>
+  <animation>
+   <frame tileid="1" duration="100"/>
+   <frame tileid="2" duration="250"/>
+  </animation>
+ </tile>
+</tileset>"#;
+		let tsx = TsxTileset::parse(tsx).unwrap();
+
+		assert_eq!(Some("World"), tsx.name.as_deref());
+		assert_eq!(
+			(16, 16, 2, 1),
+			(tsx.tile_width, tsx.tile_height, tsx.spacing, tsx.margin)
+		);
+		assert_eq!((4, 2), (tsx.tile_count, tsx.columns));
+		assert_eq!(Some("world.png"), tsx.image.as_deref());
+
+		let tile = &tsx.tiles[&1];
+		assert_eq!(vec![(1, 100), (2, 250)], tile.animation);
+		let metadata = properties_to_metadata(&tile.properties).unwrap();
+		assert_eq!(
+			ron::from_str::<ron::Value>("{\"depth\": 3}").unwrap(),
+			metadata
+		);
+	}
+
+	/// Writes a `.tsx` file with the given tileset attributes and properties along with its image
+	fn write_tsx(dir: &TempDir, name: &str, attributes: &str, properties: &str, width: u32) {
+		image::RgbaImage::new(width, 4)
+			.save(dir.path().join(format!("{}.png", name)))
+			.unwrap();
+		fs::write(
+			dir.path().join(format!("{}.tsx", name)),
+			format!(
+				r#"<tileset name="{name}" tilewidth="4" tileheight="4" {attributes}>
+					{properties}
+					<image source="{name}.png" width="{width}" height="4"/>
+				</tileset>"#
+			),
+		)
+		.unwrap();
+	}
+
+	#[test]
+	fn should_take_id_from_settings_or_property() {
+		let dir = TempDir::new("tsx_id");
+		write_tsx(&dir, "plain", r#"tilecount="2" columns="2""#, "", 8);
+		for name in ["exported", "overridden"] {
+			write_tsx(
+				&dir,
+				name,
+				r#"tilecount="2" columns="2""#,
+				r#"<properties><property name="id" type="int" value="3"/></properties>"#,
+				8,
+			);
+		}
+		let mut app = asset_app(dir.path());
+		let load = |app: &mut App, path: &str, id: Option<TilesetId>| {
+			let handle: Handle<Tileset> =
+				app.world.resource::<AssetServer>().load_with_settings(
+					path.to_string(),
+					move |settings: &mut TsxLoaderSettings| settings.id = id,
+				);
+			assert_eq!(LoadState::Loaded, wait_for_load(app, &handle));
+			*app.world
+				.resource::<Assets<Tileset>>()
+				.get(&handle)
+				.unwrap()
+				.id()
+		};
+
+		assert_eq!(TilesetId(0), load(&mut app, "plain.tsx", None));
+		assert_eq!(TilesetId(3), load(&mut app, "exported.tsx", None));
+		assert_eq!(
+			TilesetId(5),
+			load(&mut app, "overridden.tsx", Some(TilesetId(5)))
+		);
+	}
+
+	#[test]
+	fn should_reject_images_too_narrow_for_a_tile() {
+		let dir = TempDir::new("tsx_narrow");
+		write_tsx(&dir, "narrow", r#"tilecount="1""#, "", 2);
+		write_tsx(&dir, "margin", r#"tilecount="1" margin="8""#, "", 4);
+		let mut app = asset_app(dir.path());
+
+		for path in ["narrow.tsx", "margin.tsx"] {
+			let handle: Handle<Tileset> = app.world.resource::<AssetServer>().load(path);
+			assert_eq!(LoadState::Failed, wait_for_load(&mut app, &handle));
+		}
+	}
+
+	#[test]
+	fn should_export_tsx() {
+		let mut textures = Assets::<Image>::default();
+		let mut atlases = Assets::<TextureAtlas>::default();
+		let names = ["A", "B", "C", "D", "E"];
+		let entries = names
+			.into_iter()
+			.map(|name| (name.to_string(), textures.add(solid_image(4, 4))))
+			.collect();
+		let mut builder = TilesetBuilder::new(Some(3));
+		builder.padding(2).extrusion(1);
+		let tileset = builder
+			.build_from_textures("Exported", TilesetId(7), entries, &mut textures)
+			.unwrap()
+			.into_asset(&mut atlases);
+
+		let dir = TempDir::new("tsx_export");
+		let path = dir.path().join("exported.tsx");
+		tileset.export_tsx(&atlases, &textures, &path).unwrap();
+
+		let tsx = TsxTileset::parse(&fs::read(&path).unwrap()).unwrap();
+		assert_eq!(Some("Exported"), tsx.name.as_deref());
+		assert_eq!((4, 4), (tsx.tile_width, tsx.tile_height));
+		// The tile count covers the whole grid, including the unused cell in the last row
+		assert_eq!((6, 3), (tsx.tile_count, tsx.columns));
+		// Tiles are separated by the padding and the extrusion of both neighbors
+		assert_eq!((4, 1), (tsx.spacing, tsx.margin));
+		assert_eq!(Some("exported.png"), tsx.image.as_deref());
+		assert!(dir.path().join("exported.png").exists());
+
+		let mut app = asset_app(dir.path());
+		let handle: Handle<Tileset> = app.world.resource::<AssetServer>().load("exported.tsx");
+		assert_eq!(LoadState::Loaded, wait_for_load(&mut app, &handle));
+		let loaded = app
+			.world
+			.resource::<Assets<Tileset>>()
+			.get(&handle)
+			.unwrap();
+		assert_eq!(TilesetId(7), *loaded.id());
+		for name in names {
+			assert_eq!(
+				tileset.get_base_tile_index(name),
+				loaded.get_base_tile_index(name)
+			);
+		}
+		assert_eq!(tileset.tile_size(), loaded.tile_size());
+	}
+}
@@ -1,11 +1,12 @@
 use bevy_asset::Handle;
+use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use serde::{Deserialize, Serialize};
 
 /// A structure defining an animated tile
 ///
 /// Made to be easily used with [`bevy_ecs_tilemap::GPUAnimated`] component
-#[derive(Debug, Copy, Clone, Serialize)]
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, Reflect)]
 pub struct AnimatedTileData {
 	/// The speed of the animation
 	speed: f32,
@@ -13,6 +14,12 @@ pub struct AnimatedTileData {
 	start: usize,
 	/// The end index of the animation (inclusive)
 	end: usize,
+	/// How the animation plays once it reaches its last frame
+	#[serde(default)]
+	mode: AnimationMode,
+	/// Whether spawned instances start at a random point in the animation
+	#[serde(default)]
+	random_phase: bool,
 }
 
 /// A structure defining an animated tile
@@ -24,12 +31,20 @@ pub struct AnimatedTileHandle {
 	///
 	/// Each frame is a registered [`Handle`]
 	pub frames: Vec<Handle<Image>>,
+	/// The duration of each frame (in seconds), in the same order as `frames`
+	///
+	/// A `None` entry (or a missing one) means the frame uses the animation's default timing
+	pub durations: Vec<Option<f32>>,
+	/// How the animation plays once it reaches its last frame
+	pub mode: AnimationMode,
+	/// Whether spawned instances start at a random point in the animation
+	pub random_phase: bool,
 }
 
 /// A structure defining an animated tile
 ///
 /// Made to be easily used with [`bevy_ecs_tilemap::GPUAnimated`] component
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
 pub struct AnimatedTileDef {
 	/// The speed of the animation
 	///
@@ -38,7 +53,8 @@ pub struct AnimatedTileDef {
 	pub speed: f32,
 	/// The frames of the animation
 	///
-	/// Each entry is a path to a texture relative to the configuration file
+	/// Each entry is a path to a texture relative to the configuration file, optionally
+	/// paired with the duration of that frame (in seconds)
 	///
 	/// # Examples
 	///
@@ -47,19 +63,94 @@ pub struct AnimatedTileDef {
 	/// 	// ...
 	/// 	frames: [
 	/// 		"frame-001.png",
-	/// 		"frame-002.png",
+	/// 		("frame-002.png", 0.5),
 	/// 		"frame-003.png",
 	/// 	]
 	/// 	// ...
 	/// )
 	/// ```
 	#[serde(default)]
-	pub frames: Vec<String>,
+	pub frames: Vec<AnimatedFrameDef>,
+	/// How the animation plays once it reaches its last frame
+	///
+	/// Default: `Loop`
+	#[serde(default)]
+	pub mode: AnimationMode,
+	/// Whether spawned instances start at a random point in the animation
+	///
+	/// This keeps many instances of the same tile from animating in lockstep. It has no effect
+	/// on `Once` animations.
+	///
+	/// Default: false
+	#[serde(default)]
+	pub random_phase: bool,
+}
+
+/// The playback mode of an animated tile
+#[derive(Deserialize, Serialize, Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+pub enum AnimationMode {
+	/// Restart from the first frame after the last frame
+	#[default]
+	Loop,
+	/// Play through the frames once and stop on the last frame
+	Once,
+	/// Play forward to the last frame, then backward to the first frame, and repeat
+	PingPong,
+}
+
+/// A single frame of an animated tile
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
+#[serde(untagged)]
+pub enum AnimatedFrameDef {
+	/// A frame that uses the animation's default timing
+	///
+	/// Takes the form: path
+	Path(String),
+	/// A frame that is shown for a specific duration
+	///
+	/// Takes the form: (path, duration)
+	Timed(String, f32),
+}
+
+impl AnimatedFrameDef {
+	/// Gets the path to this frame's texture
+	pub fn path(&self) -> &str {
+		match self {
+			Self::Path(path) => path,
+			Self::Timed(path, ..) => path,
+		}
+	}
+
+	/// Gets the duration of this frame (in seconds), if one was given
+	pub fn duration(&self) -> Option<f32> {
+		match self {
+			Self::Path(..) => None,
+			Self::Timed(.., duration) => Some(*duration),
+		}
+	}
 }
 
 impl AnimatedTileData {
 	pub fn new(speed: f32, start: usize, end: usize) -> Self {
-		Self { speed, start, end }
+		Self {
+			speed,
+			start,
+			end,
+			mode: AnimationMode::default(),
+			random_phase: false,
+		}
+	}
+
+	/// Sets how the animation plays once it reaches its last frame
+	pub fn with_mode(mut self, mode: AnimationMode) -> Self {
+		self.mode = mode;
+		self
+	}
+
+	/// Sets whether spawned instances start at a random point in the animation
+	pub fn with_random_phase(mut self, random_phase: bool) -> Self {
+		self.random_phase = random_phase;
+		self
 	}
 
 	/// Gets the start animation index (inclusive)
@@ -77,9 +168,19 @@ impl AnimatedTileData {
 		self.speed
 	}
 
+	/// Gets how the animation plays once it reaches its last frame
+	pub fn mode(&self) -> AnimationMode {
+		self.mode
+	}
+
+	/// Checks if spawned instances start at a random point in the animation
+	pub fn random_phase(&self) -> bool {
+		self.random_phase
+	}
+
 	/// Gets the number of frames in this animation
 	pub fn frame_count(&self) -> usize {
-		self.end - self.start
+		self.end - self.start + 1
 	}
 }
 
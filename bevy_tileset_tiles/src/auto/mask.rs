@@ -0,0 +1,218 @@
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::AutoTileRule;
+
+/// A bitmask describing which neighbors of a tile match it
+///
+/// Each bit is set if the neighbor in that direction matches:
+///
+/// | Bit | Value | Direction  |
+/// |-----|-------|------------|
+/// | 0   | 1     | North      |
+/// | 1   | 2     | East       |
+/// | 2   | 4     | South      |
+/// | 3   | 8     | West       |
+/// | 4   | 16    | North-East |
+/// | 5   | 32    | South-East |
+/// | 6   | 64    | South-West |
+/// | 7   | 128   | North-West |
+///
+/// The lower four bits form the common 16-value edge (or "Wang") scheme. All eight bits form
+/// the 256 masks of the "blob" scheme, which reduce to 47 distinct tiles (see
+/// [`NeighborMask::to_blob`]).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize, Reflect)]
+#[serde(transparent)]
+pub struct NeighborMask(pub u8);
+
+impl NeighborMask {
+	pub const NONE: Self = Self(0);
+	pub const NORTH: Self = Self(1 << 0);
+	pub const EAST: Self = Self(1 << 1);
+	pub const SOUTH: Self = Self(1 << 2);
+	pub const WEST: Self = Self(1 << 3);
+	pub const NORTH_EAST: Self = Self(1 << 4);
+	pub const SOUTH_EAST: Self = Self(1 << 5);
+	pub const SOUTH_WEST: Self = Self(1 << 6);
+	pub const NORTH_WEST: Self = Self(1 << 7);
+	/// All four edges
+	pub const EDGES: Self = Self(0b0000_1111);
+	/// All four corners
+	pub const CORNERS: Self = Self(0b1111_0000);
+
+	/// Checks if all the bits in the given mask are set in this one
+	pub fn contains(&self, other: Self) -> bool { self.0 & other.0 == other.0 }
+
+	/// Returns this mask with only its edge bits (the 4-bit edge value)
+	pub fn edges(&self) -> Self { Self(self.0 & Self::EDGES.0) }
+
+	/// Gets the 4-bit corner (or 2-corner "Wang") value of this mask
+	///
+	/// A corner is filled if its neighbor and both neighboring edges match:
+	///
+	/// | Bit | Value | Corner     |
+	/// |-----|-------|------------|
+	/// | 0   | 1     | North-East |
+	/// | 1   | 2     | South-East |
+	/// | 2   | 4     | South-West |
+	/// | 3   | 8     | North-West |
+	///
+	/// returns: u8
+	///
+	pub fn corners(&self) -> u8 {
+		let blob = self.to_blob();
+		(blob.0 & Self::CORNERS.0) >> 4
+	}
+
+	/// Reduces this mask to its canonical "blob" mask
+	///
+	/// A corner only affects the look of a blob tile if both of its neighboring edges match,
+	/// so corners without both edges set are cleared. This reduces the 256 possible masks
+	/// down to the 47 distinct tiles of the blob scheme.
+	///
+	/// returns: NeighborMask
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bevy_tileset_tiles::prelude::NeighborMask;
+	/// let mask = NeighborMask::NORTH
+	/// 	.with(NeighborMask::NORTH_EAST, true)
+	/// 	.with(NeighborMask::SOUTH_WEST, true);
+	///
+	/// // Neither corner has both of its edges set
+	/// assert_eq!(NeighborMask::NORTH, mask.to_blob());
+	/// ```
+	pub fn to_blob(&self) -> Self {
+		let corners = [
+			(Self::NORTH_EAST, Self::NORTH.0 | Self::EAST.0),
+			(Self::SOUTH_EAST, Self::SOUTH.0 | Self::EAST.0),
+			(Self::SOUTH_WEST, Self::SOUTH.0 | Self::WEST.0),
+			(Self::NORTH_WEST, Self::NORTH.0 | Self::WEST.0),
+		];
+		corners.into_iter().fold(*self, |mask, (corner, edges)| {
+			mask.with(corner, mask.contains(corner) && self.contains(Self(edges)))
+		})
+	}
+
+	/// Checks if this mask is one of the 47 canonical "blob" masks
+	pub fn is_blob(&self) -> bool { self.to_blob() == *self }
+
+	/// Returns the 47 canonical "blob" masks in ascending order
+	pub fn blob_masks() -> impl Iterator<Item = Self> {
+		(0..=u8::MAX).map(Self).filter(Self::is_blob)
+	}
+
+	/// Gets the position (from 0 to 46) of this mask's canonical "blob" mask within
+	/// [`NeighborMask::blob_masks`]
+	///
+	/// This is useful for blob tilesets laid out in the order of their masks.
+	pub fn blob_index(&self) -> usize {
+		let blob = self.to_blob();
+		Self::blob_masks().take_while(|mask| *mask != blob).count()
+	}
+
+	/// Returns this mask with the given bits set (or unset)
+	pub fn with(self, other: Self, value: bool) -> Self {
+		if value {
+			Self(self.0 | other.0)
+		} else {
+			Self(self.0 & !other.0)
+		}
+	}
+}
+
+impl From<u8> for NeighborMask {
+	fn from(value: u8) -> Self { Self(value) }
+}
+
+impl From<AutoTileRule> for NeighborMask {
+	/// Creates a mask where only the directions that must match are set
+	fn from(rule: AutoTileRule) -> Self {
+		[
+			(rule.north, Self::NORTH),
+			(rule.east, Self::EAST),
+			(rule.south, Self::SOUTH),
+			(rule.west, Self::WEST),
+			(rule.north_east, Self::NORTH_EAST),
+			(rule.south_east, Self::SOUTH_EAST),
+			(rule.south_west, Self::SOUTH_WEST),
+			(rule.north_west, Self::NORTH_WEST),
+		]
+		.into_iter()
+		.fold(Self::NONE, |mask, (value, bit)| {
+			mask.with(bit, value == Some(true))
+		})
+	}
+}
+
+impl From<NeighborMask> for AutoTileRule {
+	/// Creates a fully specified rule, where each direction must match its bit in the mask
+	fn from(mask: NeighborMask) -> Self {
+		Self {
+			north: Some(mask.contains(NeighborMask::NORTH)),
+			east: Some(mask.contains(NeighborMask::EAST)),
+			south: Some(mask.contains(NeighborMask::SOUTH)),
+			west: Some(mask.contains(NeighborMask::WEST)),
+			north_east: Some(mask.contains(NeighborMask::NORTH_EAST)),
+			north_west: Some(mask.contains(NeighborMask::NORTH_WEST)),
+			south_east: Some(mask.contains(NeighborMask::SOUTH_EAST)),
+			south_west: Some(mask.contains(NeighborMask::SOUTH_WEST)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::prelude::{AutoTileRule, NeighborMask};
+
+	#[test]
+	fn should_reduce_to_blob() {
+		assert_eq!(47, NeighborMask::blob_masks().count());
+		assert_eq!(NeighborMask(255), NeighborMask(255).to_blob());
+		assert_eq!(NeighborMask::EDGES, NeighborMask::EDGES.to_blob());
+		assert_eq!(NeighborMask::NONE, NeighborMask::CORNERS.to_blob());
+		assert_eq!(0, NeighborMask::CORNERS.blob_index());
+		assert_eq!(46, NeighborMask(255).blob_index());
+
+		for value in 0..=u8::MAX {
+			let mask = NeighborMask(value);
+			let rule = AutoTileRule::from_blob(mask.to_blob());
+			assert!(rule.is_subset_of(&mask.into()));
+		}
+	}
+
+	#[test]
+	fn should_get_corners() {
+		let mask = NeighborMask::EDGES
+			.with(NeighborMask::NORTH_EAST, true)
+			.with(NeighborMask::SOUTH_WEST, true);
+		assert_eq!(0b0101, mask.corners());
+
+		// The south-east corner is missing an edge
+		let mask = NeighborMask::NORTH
+			.with(NeighborMask::EAST, true)
+			.with(NeighborMask::NORTH_EAST, true)
+			.with(NeighborMask::SOUTH_EAST, true);
+		assert_eq!(0b0001, mask.corners());
+		assert_eq!(mask, NeighborMask::from(AutoTileRule::from(mask)));
+	}
+
+	#[test]
+	fn should_convert_to_rule() {
+		let mask = NeighborMask::NORTH.with(NeighborMask::WEST, true);
+		let rule: AutoTileRule = mask.into();
+
+		assert_eq!(Some(true), rule.north);
+		assert_eq!(Some(true), rule.west);
+		assert_eq!(Some(false), rule.east);
+		assert_eq!(Some(false), rule.south_west);
+
+		let edge_rule = AutoTileRule {
+			north: Some(true),
+			west: Some(true),
+			..Default::default()
+		};
+		assert!(edge_rule.is_subset_of(&rule));
+	}
+}
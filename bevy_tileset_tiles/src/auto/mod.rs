@@ -1,19 +1,30 @@
+mod mask;
 mod rules;
+mod ruleset;
 
 use crate::prelude::{VariantTileData, VariantTileDef, VariantTileHandle};
+use bevy_reflect::Reflect;
+pub use mask::NeighborMask;
 pub use rules::AutoTileRule;
+pub use ruleset::{AutoTileRuleset, AutoTileRulesetDef, AutoTileRulesetEntry};
 use serde::{Deserialize, Serialize};
 
 /// A structure defining an auto tile
 ///
 /// An auto tile contains rules that are applied when placed, removed, or changed
 /// to itself and to its neighbors of the same type
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Reflect)]
 pub struct AutoTileData {
 	/// The rule defining this tile
 	rule: AutoTileRule,
 	/// The underlying tile variants
 	variants: Vec<VariantTileData>,
+	/// Whether this tile is used when no rule matches
+	#[serde(default)]
+	fallback: bool,
+	/// The filled corners of this tile, if it's matched by its corners
+	#[serde(default)]
+	corners: Option<u8>,
 }
 
 /// A structure defining an auto tile
@@ -23,25 +34,64 @@ pub struct AutoTileHandle {
 	pub rule: AutoTileRule,
 	/// The underlying variant handles
 	pub variants: Vec<VariantTileHandle>,
+	/// Whether this tile is used when no rule matches
+	pub fallback: bool,
+	/// The filled corners of this tile, if it's matched by its corners
+	pub corners: Option<u8>,
 }
 
 /// A structure defining an auto tile
 ///
 /// An auto tile contains rules that are applied when placed, removed, or changed
 /// to itself and to its neighbors of the same type
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
 pub struct AutoTileDef {
 	/// The rule defining this tile
 	#[serde(default)]
 	pub rule: AutoTileRule,
+	/// The "blob" mask this tile is used for
+	///
+	/// When set, this replaces `rule` with one matching every neighbor configuration that
+	/// reduces to this mask (see [`AutoTileRule::from_blob`]). This allows each of the 47 tiles
+	/// of a blob tileset to be authored by its canonical mask.
+	#[serde(default)]
+	pub mask: Option<NeighborMask>,
 	/// The underlying tile variants
 	#[serde(default)]
 	pub variants: Vec<VariantTileDef>,
+	/// Whether this tile is used when no rule matches
+	///
+	/// Without a fallback, the last tile is used instead.
+	#[serde(default)]
+	pub fallback: bool,
+	/// The filled corners of this tile, for corner-matching (or 2-corner "Wang") auto tiles
+	///
+	/// When set, this replaces `rule` and the tile only matches when exactly these corners are
+	/// filled (see [`NeighborMask::corners`] for the bit layout). A corner is filled if its
+	/// neighbor and both neighboring edges match. Otherwise, the tile is matched by its edges.
+	#[serde(default)]
+	pub corners: Option<u8>,
+}
+
+impl AutoTileDef {
+	/// Gets the rule defining this tile, taking its blob `mask` into account
+	pub fn rule(&self) -> AutoTileRule { self.mask.map_or(self.rule, AutoTileRule::from_blob) }
 }
 
 impl AutoTileData {
 	pub fn new(rule: AutoTileRule, variants: Vec<VariantTileData>) -> Self {
-		AutoTileData { rule, variants }
+		AutoTileData {
+			rule,
+			variants,
+			fallback: false,
+			corners: None,
+		}
+	}
+
+	/// Sets whether this tile is used when no rule matches
+	pub fn with_fallback(mut self, fallback: bool) -> Self {
+		self.fallback = fallback;
+		self
 	}
 
 	/// Gets the rule associated with this auto tile
@@ -49,6 +99,37 @@ impl AutoTileData {
 		self.rule
 	}
 
+	/// Sets the filled corners of this tile, making it matched by its corners
+	pub fn with_corners(mut self, corners: Option<u8>) -> Self {
+		self.corners = corners;
+		self
+	}
+
+	/// Gets the filled corners of this tile, if it's matched by its corners
+	pub fn corners(&self) -> Option<u8> { self.corners }
+
+	/// Checks if this tile matches the given neighbors
+	///
+	/// Tiles matched by their corners compare their corners with the filled corners of the
+	/// neighbors, treating unspecified directions as not matching. All other tiles match if
+	/// their rule is a subset of the given one.
+	///
+	/// # Arguments
+	///
+	/// * `neighbors`: The rule describing the neighbors
+	///
+	/// returns: bool
+	///
+	pub fn matches(&self, neighbors: &AutoTileRule) -> bool {
+		match self.corners {
+			Some(corners) => NeighborMask::from(*neighbors).corners() == corners & 0b1111,
+			None => self.rule.is_subset_of(neighbors),
+		}
+	}
+
+	/// Checks if this tile is used when no rule matches
+	pub fn is_fallback(&self) -> bool { self.fallback }
+
 	/// Gets the underlying tile variants
 	pub fn variants(&self) -> &Vec<VariantTileData> {
 		&self.variants
@@ -1,12 +1,15 @@
+use bevy_reflect::Reflect;
 use serde::{Deserialize, Serialize};
 
+use crate::prelude::NeighborMask;
+
 /// The rules used to define an auto tile
 ///
 /// The possible states are:
 /// * `Some(true)` -> Must Match
 /// * `Some(false)` -> Must Not Match
 /// * `None` -> Ignore
-#[derive(Debug, Default, Deserialize, Copy, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Default, Deserialize, Copy, Clone, Eq, PartialEq, Serialize, Reflect)]
 pub struct AutoTileRule {
 	#[serde(alias = "n")]
 	#[serde(default)]
@@ -110,6 +113,50 @@ impl AutoTileRule {
 		}
 	}
 
+	/// Returns a rule matching every mask that reduces to the given "blob" mask
+	///
+	/// The edges are always checked, while each corner is only checked if both of its
+	/// neighboring edges are set in the mask (see [`NeighborMask::to_blob`]).
+	///
+	/// # Arguments
+	///
+	/// * `mask`: The blob mask to match
+	///
+	/// returns: AutoTileRule
+	///
+	pub fn from_blob(mask: NeighborMask) -> Self {
+		let mask = mask.to_blob();
+		let corner = |corner: NeighborMask, a: NeighborMask, b: NeighborMask| {
+			(mask.contains(a) && mask.contains(b)).then_some(mask.contains(corner))
+		};
+		Self {
+			north: Some(mask.contains(NeighborMask::NORTH)),
+			east: Some(mask.contains(NeighborMask::EAST)),
+			south: Some(mask.contains(NeighborMask::SOUTH)),
+			west: Some(mask.contains(NeighborMask::WEST)),
+			north_east: corner(
+				NeighborMask::NORTH_EAST,
+				NeighborMask::NORTH,
+				NeighborMask::EAST,
+			),
+			north_west: corner(
+				NeighborMask::NORTH_WEST,
+				NeighborMask::NORTH,
+				NeighborMask::WEST,
+			),
+			south_east: corner(
+				NeighborMask::SOUTH_EAST,
+				NeighborMask::SOUTH,
+				NeighborMask::EAST,
+			),
+			south_west: corner(
+				NeighborMask::SOUTH_WEST,
+				NeighborMask::SOUTH,
+				NeighborMask::WEST,
+			),
+		}
+	}
+
 	fn check_bool(lhs: Option<bool>, rhs: Option<bool>) -> bool {
 		match lhs {
 			Some(l_val) => match rhs {
@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{AutoTileDef, AutoTileRule, NeighborMask, VariantTileDef};
+
+/// A set of named auto tile rules, defined in its own file
+///
+/// A rule set describes the topology of an auto tile once so that many tiles can share it,
+/// each supplying its own sub-tiles through an [`AutoTileRulesetDef`].
+///
+/// # Examples
+///
+/// ```ron
+/// #![enable(implicit_some)]
+/// (
+/// 	rules: [
+/// 		(name: "center", rule: (n: true, e: true, s: true, w: true)),
+/// 		(name: "top", rule: (n: false, s: true)),
+/// 		(name: "island", mask: 0),
+/// 	]
+/// )
+/// ```
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Reflect)]
+pub struct AutoTileRuleset {
+	/// The named rules
+	///
+	/// > __Order here is important!__ Make sure rules are listed in order of
+	/// > descending restriction (i.e. the first item being the most restrictive)
+	#[serde(default)]
+	pub rules: Vec<AutoTileRulesetEntry>,
+}
+
+/// A single named rule within an [`AutoTileRuleset`]
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
+pub struct AutoTileRulesetEntry {
+	/// The name used by tiles to provide the sub-tiles for this rule
+	pub name: String,
+	/// The rule to match
+	#[serde(default)]
+	pub rule: AutoTileRule,
+	/// The "blob" mask to match (see [`AutoTileDef::mask`])
+	#[serde(default)]
+	pub mask: Option<NeighborMask>,
+	/// The filled corners to match (see [`AutoTileDef::corners`])
+	#[serde(default)]
+	pub corners: Option<u8>,
+}
+
+/// A structure defining an auto tile using the rules of a shared [`AutoTileRuleset`]
+///
+/// The rule set is read and combined with these sub-tiles when the tile is loaded, resulting
+/// in a regular auto tile.
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
+pub struct AutoTileRulesetDef {
+	/// The path to the rule set file, relative to the file defining the tile
+	pub ruleset: String,
+	/// The variants of each sub-tile, keyed by the name of their rule in the rule set
+	///
+	/// Rules without an entry are skipped.
+	#[serde(default)]
+	pub tiles: HashMap<String, Vec<VariantTileDef>>,
+	/// The name of the rule whose sub-tile is used when no rule matches
+	///
+	/// Without a fallback, the sub-tile of the last rule is used instead.
+	#[serde(default)]
+	pub fallback: Option<String>,
+}
+
+impl AutoTileRulesetDef {
+	/// Combines the sub-tiles of this tile with the rules of the given rule set
+	///
+	/// The resulting auto tiles are in the same order as the rules in the rule set.
+	///
+	/// # Arguments
+	///
+	/// * `ruleset`: The rule set referenced by this tile
+	///
+	/// returns: Result<Vec<AutoTileDef>, String>
+	///
+	pub fn resolve(&self, ruleset: &AutoTileRuleset) -> Result<Vec<AutoTileDef>, String> {
+		let mut names = HashSet::with_capacity(ruleset.rules.len());
+		for entry in &ruleset.rules {
+			if !names.insert(entry.name.as_str()) {
+				return Err(format!(
+					"a rule set defining {:?} more than once",
+					entry.name
+				));
+			}
+		}
+		let mut unknown = self
+			.tiles
+			.keys()
+			.filter(|name| !names.contains(name.as_str()))
+			.collect::<Vec<_>>();
+		if !unknown.is_empty() {
+			unknown.sort();
+			return Err(format!("unknown rules {:?}", unknown));
+		}
+		if let Some(fallback) = self.fallback.as_ref() {
+			if !self.tiles.contains_key(fallback) {
+				return Err(format!("no sub-tiles for its fallback rule {:?}", fallback));
+			}
+		}
+
+		Ok(ruleset
+			.rules
+			.iter()
+			.filter_map(|entry| {
+				Some(AutoTileDef {
+					rule: entry.rule,
+					mask: entry.mask,
+					variants: self.tiles.get(&entry.name)?.clone(),
+					fallback: self.fallback.as_ref() == Some(&entry.name),
+					corners: entry.corners,
+				})
+			})
+			.collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::prelude::{AutoTileRuleset, AutoTileRulesetDef};
+
+	#[test]
+	fn should_resolve_ruleset() {
+		let ruleset: AutoTileRuleset = ron::from_str(
+			r#"#![enable(implicit_some)]
+			(rules: [
+				(name: "center", rule: (n: true, s: true)),
+				(name: "edge", rule: (n: true)),
+				(name: "island", mask: Some(0)),
+			])"#,
+		)
+		.unwrap();
+		let def: AutoTileRulesetDef = ron::from_str(
+			r#"(
+				ruleset: "blob.ron",
+				tiles: {
+					"island": [(tile: Standard("island.png"))],
+					"center": [(tile: Standard("center.png"))],
+				},
+			)"#,
+		)
+		.unwrap();
+
+		let autos = def.resolve(&ruleset).unwrap();
+		assert_eq!(2, autos.len());
+		assert_eq!(Some(true), autos[0].rule().south);
+		assert_eq!(Some(false), autos[1].rule().north);
+
+		let mut def = def;
+		def.fallback = Some(String::from("island"));
+		let autos = def.resolve(&ruleset).unwrap();
+		assert!(!autos[0].fallback);
+		assert!(autos[1].fallback);
+
+		def.fallback = Some(String::from("edge"));
+		assert!(def.resolve(&ruleset).is_err());
+
+		def.fallback = None;
+		def.tiles.insert(String::from("corner"), Vec::new());
+		assert!(def.resolve(&ruleset).is_err());
+	}
+}
@@ -0,0 +1,31 @@
+use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+
+/// A simple collision shape for a tile
+///
+/// All coordinates are in pixels, relative to the tile's bottom-left corner (with +Y pointing up).
+/// This keeps the data independent of any particular physics engine.
+///
+/// # Examples
+///
+/// ```ron
+/// (
+/// 	// ...
+/// 	collision: Some(Rect(min: (0.0, 0.0), max: (16.0, 8.0))),
+/// )
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Reflect)]
+// Reflected as an opaque value since deriving field-wise reflection for a recursive type overflows
+#[reflect_value(Debug, PartialEq, Serialize, Deserialize)]
+pub enum TileCollision {
+	/// Covers the entire tile
+	Full,
+	/// An axis-aligned rectangle
+	Rect { min: (f32, f32), max: (f32, f32) },
+	/// A circle
+	Circle { center: (f32, f32), radius: f32 },
+	/// A closed polygon defined by its points (in order)
+	Polygon(Vec<(f32, f32)>),
+	/// A collection of shapes
+	Compound(Vec<TileCollision>),
+}
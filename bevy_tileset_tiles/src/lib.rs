@@ -1,9 +1,16 @@
 //! Tile data, including tile definitions (for config files) and auto tiling
 pub mod prelude {
-	pub use super::animated::{AnimatedTileData, AnimatedTileDef, AnimatedTileHandle};
+	pub use super::animated::{
+		AnimatedFrameDef, AnimatedTileData, AnimatedTileDef, AnimatedTileHandle, AnimationMode,
+	};
 	#[cfg(feature = "auto-tile")]
-	pub use super::auto::{AutoTileData, AutoTileDef, AutoTileHandle, AutoTileRule};
+	pub use super::auto::{
+		AutoTileData, AutoTileDef, AutoTileHandle, AutoTileRule, AutoTileRuleset,
+		AutoTileRulesetDef, AutoTileRulesetEntry, NeighborMask,
+	};
+	pub use super::collision::TileCollision;
 	pub use super::tile::{TileData, TileDef, TileDefType, TileHandle, TileHandleType, TileType};
+	pub use super::transform::{TileReferenceDef, TileRotation, TileTransform};
 	#[cfg(feature = "variants")]
 	pub use super::variants::{
 		SimpleTileDefType, SimpleTileHandle, SimpleTileType, VariantTileData, VariantTileDef,
@@ -14,6 +21,8 @@ pub mod prelude {
 pub mod animated;
 #[cfg(feature = "auto-tile")]
 pub mod auto;
+pub mod collision;
 pub mod tile;
+pub mod transform;
 #[cfg(feature = "variants")]
 pub mod variants;
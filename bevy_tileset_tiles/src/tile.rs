@@ -1,24 +1,44 @@
-use bevy_asset::{AssetServer, Handle, LoadState};
+use bevy_asset::{Asset, AssetServer, Handle, LoadState};
+use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
+use ron::Value;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "auto-tile")]
 use crate::auto::*;
-use crate::prelude::{AnimatedTileData, AnimatedTileDef, AnimatedTileHandle};
+use crate::prelude::{
+	AnimatedTileData,
+	AnimatedTileDef,
+	AnimatedTileHandle,
+	TileCollision,
+	TileReferenceDef,
+	TileTransform,
+};
 #[cfg(feature = "variants")]
 use crate::variants::*;
 
 /// Top-level structure defining a tile
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Reflect)]
 pub struct TileData {
 	/// The name of this tile
 	name: String,
 	/// The actual tile data
 	tile: TileType,
+	/// Arbitrary user data attached to this tile
+	#[reflect(ignore)]
+	metadata: Option<Value>,
+	/// The tags used to categorize this tile
+	tags: Vec<String>,
+	/// The group this tile belongs to
+	group: Option<String>,
+	/// The collision shape of this tile
+	collision: Option<TileCollision>,
+	/// The transform applied to this tile when displayed
+	transform: TileTransform,
 }
 
 /// An enum defining the tile's type
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Reflect)]
 pub enum TileType {
 	/// A standard tile
 	Standard(usize),
@@ -37,6 +57,14 @@ pub enum TileType {
 pub struct TileHandle {
 	pub name: String,
 	pub tile: TileHandleType,
+	/// Arbitrary user data attached to this tile
+	pub metadata: Option<Value>,
+	/// The tags used to categorize this tile
+	pub tags: Vec<String>,
+	/// The group this tile belongs to
+	pub group: Option<String>,
+	/// The collision shape of this tile
+	pub collision: Option<TileCollision>,
 }
 
 /// An enum defining the tile's type
@@ -48,19 +76,65 @@ pub enum TileHandleType {
 	Variant(Vec<VariantTileHandle>),
 	#[cfg(feature = "auto-tile")]
 	Auto(Vec<AutoTileHandle>),
+	Reference(TileReferenceDef),
 }
 
 /// Top-level tile definition structure
-#[derive(Deserialize, Serialize, Debug, Clone)]
+///
+/// This is also an asset, so it can be loaded on its own from a `.tile.ron` file.
+#[derive(Asset, Reflect, Deserialize, Serialize, Debug, Clone)]
 pub struct TileDef {
 	/// The name of this tile
 	pub name: String,
 	/// The actual tile data
 	pub tile: TileDefType,
+	/// Arbitrary user data attached to this tile
+	///
+	/// # Examples
+	///
+	/// ```ron
+	/// (
+	/// 	// ...
+	/// 	metadata: Some({
+	/// 		"movement_cost": 2,
+	/// 		"material": "stone",
+	/// 	}),
+	/// )
+	/// ```
+	#[serde(default)]
+	#[reflect(ignore)]
+	pub metadata: Option<Value>,
+	/// The tags used to categorize this tile (e.g. `["walkable", "grass"]`)
+	#[serde(default)]
+	pub tags: Vec<String>,
+	/// The group this tile belongs to (e.g. `"water"`)
+	///
+	/// Unlike tags, a tile belongs to at most one group
+	#[serde(default)]
+	pub group: Option<String>,
+	/// The collision shape of this tile
+	#[serde(default)]
+	pub collision: Option<TileCollision>,
+}
+
+impl TileDef {
+	pub fn new_standard<TName: Into<String>, TPath: Into<String>>(
+		name: TName,
+		path: TPath,
+	) -> Self {
+		Self {
+			name: name.into(),
+			tile: TileDefType::Standard(path.into()),
+			metadata: None,
+			tags: Vec::new(),
+			group: None,
+			collision: None,
+		}
+	}
 }
 
 /// An enum defining the tile's type
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
 pub enum TileDefType {
 	/// Defines a plain old tile
 	Standard(String),
@@ -75,6 +149,13 @@ pub enum TileDefType {
 	/// > descending rule restriction (i.e. the first item being the most restrictive)
 	#[cfg(feature = "auto-tile")]
 	Auto(Vec<AutoTileDef>),
+	/// Defines a set of tiles that chooses the one matching a rule from a shared rule set file
+	///
+	/// The rule set is resolved by the tileset loaders, which turn this into an `Auto` tile.
+	#[cfg(feature = "auto-tile")]
+	AutoRuleset(AutoTileRulesetDef),
+	/// Defines a tile that reuses another tile's texture(s) with a transform applied
+	Reference(TileReferenceDef),
 }
 
 impl TileData {
@@ -97,7 +178,47 @@ impl TileData {
 	/// 	TileType::Standard(some_texture_index)
 	/// );
 	/// ```
-	pub fn new(name: String, tile: TileType) -> Self { Self { name, tile } }
+	pub fn new(name: String, tile: TileType) -> Self {
+		Self {
+			name,
+			tile,
+			metadata: None,
+			tags: Vec::new(),
+			group: None,
+			collision: None,
+			transform: TileTransform::default(),
+		}
+	}
+
+	/// Sets the user data attached to this tile
+	pub fn with_metadata(mut self, metadata: Option<Value>) -> Self {
+		self.metadata = metadata;
+		self
+	}
+
+	/// Sets the tags used to categorize this tile
+	pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+		self.tags = tags;
+		self
+	}
+
+	/// Sets the group this tile belongs to
+	pub fn with_group(mut self, group: Option<String>) -> Self {
+		self.group = group;
+		self
+	}
+
+	/// Sets the collision shape of this tile
+	pub fn with_collision(mut self, collision: Option<TileCollision>) -> Self {
+		self.collision = collision;
+		self
+	}
+
+	/// Sets the transform applied to this tile when displayed
+	pub fn with_transform(mut self, transform: TileTransform) -> Self {
+		self.transform = transform;
+		self
+	}
 
 	/// Gets the name of this tile
 	pub fn name(&self) -> &str { &self.name }
@@ -105,6 +226,24 @@ impl TileData {
 	/// Gets the underlying tile data
 	pub fn tile(&self) -> &TileType { &self.tile }
 
+	/// Gets the user data attached to this tile
+	pub fn metadata(&self) -> Option<&Value> { self.metadata.as_ref() }
+
+	/// Gets the tags used to categorize this tile
+	pub fn tags(&self) -> &[String] { &self.tags }
+
+	/// Checks if this tile has the given tag
+	pub fn has_tag(&self, tag: &str) -> bool { self.tags.iter().any(|t| t == tag) }
+
+	/// Gets the group this tile belongs to
+	pub fn group(&self) -> Option<&str> { self.group.as_deref() }
+
+	/// Gets the collision shape of this tile
+	pub fn collision(&self) -> Option<&TileCollision> { self.collision.as_ref() }
+
+	/// Gets the transform applied to this tile when displayed
+	pub fn transform(&self) -> &TileTransform { &self.transform }
+
 	/// Checks if the underlying tile is a [`TileType::Standard`] tile
 	pub fn is_standard(&self) -> bool { matches!(self.tile, TileType::Standard(..)) }
 
@@ -149,6 +288,10 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Standard(handle),
+			metadata: None,
+			tags: Vec::new(),
+			group: None,
+			collision: None,
 		}
 	}
 
@@ -156,6 +299,10 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Animated(handle),
+			metadata: None,
+			tags: Vec::new(),
+			group: None,
+			collision: None,
 		}
 	}
 
@@ -164,6 +311,10 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Variant(handles.clone()),
+			metadata: None,
+			tags: Vec::new(),
+			group: None,
+			collision: None,
 		}
 	}
 
@@ -172,6 +323,21 @@ impl TileHandle {
 		Self {
 			name: name.into(),
 			tile: TileHandleType::Auto(handles.clone()),
+			metadata: None,
+			tags: Vec::new(),
+			group: None,
+			collision: None,
+		}
+	}
+
+	pub fn new_reference<TName: Into<String>>(name: TName, reference: TileReferenceDef) -> Self {
+		Self {
+			name: name.into(),
+			tile: TileHandleType::Reference(reference),
+			metadata: None,
+			tags: Vec::new(),
+			group: None,
+			collision: None,
 		}
 	}
 
@@ -206,6 +372,7 @@ impl TileHandle {
 			TileHandleType::Auto(autos) => Box::new(iter_variant_handles(
 				autos.iter().flat_map(|auto| auto.variants.iter()),
 			)),
+			TileHandleType::Reference(..) => Box::new(std::iter::empty()),
 		}
 	}
 }
@@ -248,6 +415,9 @@ mod tests {
 			AnimatedTileHandle {
 				speed: 1.0,
 				frames: vec![Handle::default(); 3],
+				durations: Vec::new(),
+				mode: Default::default(),
+				random_phase: false,
 			},
 		);
 		let mut anim_iter = anim.iter_handles();
@@ -274,6 +444,9 @@ mod tests {
 					tile: SimpleTileHandle::Animated(AnimatedTileHandle {
 						speed: 1.0,
 						frames: vec![Handle::default(); 3],
+						durations: Vec::new(),
+						mode: Default::default(),
+						random_phase: false,
 					}),
 				},
 			],
@@ -297,6 +470,8 @@ mod tests {
 			vec![
 				AutoTileHandle {
 					rule: AutoTileRule::default(),
+					fallback: false,
+					corners: None,
 					variants: vec![
 						VariantTileHandle {
 							weight: 1.0,
@@ -307,12 +482,17 @@ mod tests {
 							tile: SimpleTileHandle::Animated(AnimatedTileHandle {
 								speed: 1.0,
 								frames: vec![Handle::default(); 3],
+								durations: Vec::new(),
+								mode: Default::default(),
+								random_phase: false,
 							}),
 						},
 					],
 				},
 				AutoTileHandle {
 					rule: AutoTileRule::default(),
+					fallback: false,
+					corners: None,
 					variants: vec![
 						VariantTileHandle {
 							weight: 1.0,
@@ -323,6 +503,9 @@ mod tests {
 							tile: SimpleTileHandle::Animated(AnimatedTileHandle {
 								speed: 1.0,
 								frames: vec![Handle::default(); 3],
+								durations: Vec::new(),
+								mode: Default::default(),
+								random_phase: false,
 							}),
 						},
 					],
@@ -0,0 +1,88 @@
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+/// A transform applied to a tile when it is displayed
+///
+/// This allows a single texture to be reused for multiple tiles (e.g. the four
+/// directions of a wall) without taking up additional space in the atlas.
+#[derive(Deserialize, Serialize, Debug, Default, Copy, Clone, Eq, PartialEq, Reflect)]
+pub struct TileTransform {
+	/// Whether the tile is flipped horizontally
+	pub flip_x: bool,
+	/// Whether the tile is flipped vertically
+	pub flip_y: bool,
+	/// The clockwise rotation of the tile
+	///
+	/// Rotation is applied after flipping
+	pub rotation: TileRotation,
+}
+
+/// A clockwise rotation of a tile in quarter turns
+#[derive(Deserialize, Serialize, Debug, Default, Copy, Clone, Eq, PartialEq, Reflect)]
+pub enum TileRotation {
+	#[default]
+	None,
+	Cw90,
+	Cw180,
+	Cw270,
+}
+
+/// Defines a tile that reuses the texture(s) of another tile
+///
+/// # Examples
+///
+/// ```ron
+/// (
+/// 	name: "Wall East",
+/// 	tile: Reference((
+/// 		tile: "Wall North",
+/// 		rotation: Cw90,
+/// 	)),
+/// )
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
+pub struct TileReferenceDef {
+	/// The name of the referenced tile
+	///
+	/// This tile must be added before the referencing tile (i.e. have a lower group ID in the
+	/// tileset). If the referenced tile is itself a reference, only this reference's transform
+	/// is used (transforms are not combined).
+	pub tile: String,
+	/// Whether the tile is flipped horizontally
+	#[serde(default)]
+	pub flip_x: bool,
+	/// Whether the tile is flipped vertically
+	#[serde(default)]
+	pub flip_y: bool,
+	/// The clockwise rotation of the tile
+	#[serde(default)]
+	pub rotation: TileRotation,
+}
+
+impl TileTransform {
+	/// Returns true if this transform leaves the tile unchanged
+	pub fn is_identity(&self) -> bool { *self == Self::default() }
+}
+
+impl TileRotation {
+	/// Gets the clockwise rotation in radians
+	pub fn radians(&self) -> f32 {
+		match self {
+			Self::None => 0.0,
+			Self::Cw90 => std::f32::consts::FRAC_PI_2,
+			Self::Cw180 => std::f32::consts::PI,
+			Self::Cw270 => 3.0 * std::f32::consts::FRAC_PI_2,
+		}
+	}
+}
+
+impl TileReferenceDef {
+	/// Gets the transform applied to the referenced tile
+	pub fn transform(&self) -> TileTransform {
+		TileTransform {
+			flip_x: self.flip_x,
+			flip_y: self.flip_y,
+			rotation: self.rotation,
+		}
+	}
+}
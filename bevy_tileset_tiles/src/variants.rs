@@ -1,5 +1,6 @@
 use crate::prelude::{AnimatedTileData, AnimatedTileDef, AnimatedTileHandle};
 use bevy_asset::Handle;
+use bevy_reflect::Reflect;
 use bevy_render::texture::Image;
 use serde::{Deserialize, Serialize};
 
@@ -7,7 +8,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// A _variant_ essentially wraps a [simple](SimpleTileType) tile and gives it
 /// a weight. This weight is used to define how likely it should be picked at random
-#[derive(Debug, Copy, Clone, Serialize)]
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, Reflect)]
 pub struct VariantTileData {
 	/// The weight of this variant (used for random sampling)
 	weight: f32,
@@ -19,7 +20,7 @@ pub struct VariantTileData {
 ///
 /// These are "simple" types in that their inner types are not _too_ complex
 /// or heavily nested
-#[derive(Debug, Copy, Clone, Serialize)]
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, Reflect)]
 pub enum SimpleTileType {
 	Standard(usize),
 	Animated(AnimatedTileData),
@@ -45,7 +46,7 @@ pub enum SimpleTileHandle {
 ///
 /// A _variant_ essentially wraps a [simple](SimpleTileDefType) tile and gives it
 /// a weight. This weight is used to define how likely it should be picked at random
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
 pub struct VariantTileDef {
 	/// The weight of this variant (used for random sampling)
 	///
@@ -60,7 +61,7 @@ pub struct VariantTileDef {
 ///
 /// These are "simple" types in that their inner types are not _too_ complex
 /// or heavily nested
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, Reflect)]
 pub enum SimpleTileDefType {
 	Standard(String),
 	Animated(AnimatedTileDef),
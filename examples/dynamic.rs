@@ -11,7 +11,10 @@
 //! needed. Luckily, you can convert it to a `Tileset` and add it to the `Assets<Tileset>` resource
 //! any time.
 
-use bevy::asset::FileAssetIo;
+use std::path::Path;
+
+use bevy::asset::io::AssetSourceId;
+use bevy::asset::AsyncReadExt;
 use bevy::prelude::*;
 use bevy_tileset::prelude::*;
 
@@ -48,18 +51,15 @@ impl Default for MyTileset {
 /// Starts the tileset loading process
 fn load_tileset(mut my_tileset: ResMut<MyTileset>, asset_server: Res<AssetServer>) {
 	// You can dynamically load the TileDef config files
-	let asset_path = FileAssetIo::get_base_path().join("assets");
-	let dirt_path = asset_path.join("tiles/dirt.ron");
-	let glass_path = asset_path.join("tiles/glass.ron");
-
-	let dirt_bytes = std::fs::read(dirt_path).unwrap();
-	let glass_bytes = std::fs::read(glass_path).unwrap();
+	let dirt_bytes = read_asset(&asset_server, "tiles/dirt.ron");
+	let glass_bytes = read_asset(&asset_server, "tiles/glass.ron");
 
 	let dirt_tile = ron::de::from_bytes::<TileDef>(&dirt_bytes).unwrap();
 	let glass_tile = ron::de::from_bytes::<TileDef>(&glass_bytes).unwrap();
 
 	// Automatically generate the TileHandle collection
-	let mut handles = load_tile_handles(vec![dirt_tile, glass_tile], &asset_server);
+	let mut handles =
+		load_tile_handles(vec![dirt_tile, glass_tile], &mut asset_server.clone()).unwrap();
 
 	// You can also manually construct the TileHandle yourself
 	let grass_handle: Handle<Image> = asset_server.load("tiles/grass.png");
@@ -69,6 +69,20 @@ fn load_tileset(mut my_tileset: ResMut<MyTileset>, asset_server: Res<AssetServer
 	my_tileset.tiles = Some(handles);
 }
 
+/// Reads the bytes of an asset through the `AssetServer`'s default asset source
+///
+/// This resolves paths the same way `asset_server.load` does, so it respects the asset folder
+/// configured in the `AssetPlugin` rather than assuming the assets live in `assets/`.
+fn read_asset(asset_server: &AssetServer, path: &str) -> Vec<u8> {
+	let source = asset_server.get_source(AssetSourceId::Default).unwrap();
+	bevy::tasks::block_on(async {
+		let mut reader = source.reader().read(Path::new(path)).await.unwrap();
+		let mut bytes = Vec::new();
+		reader.read_to_end(&mut bytes).await.unwrap();
+		bytes
+	})
+}
+
 fn check_loaded(
 	mut my_tileset: ResMut<MyTileset>,
 	asset_server: Res<AssetServer>,
@@ -102,7 +116,7 @@ fn check_loaded(
 	}
 
 	let raw_tileset = builder
-		.build("My Dynamic Tileset", 123, &mut textures)
+		.build("My Dynamic Tileset", TilesetId(123), &mut textures)
 		.unwrap();
 
 	// We could also choose to add it to the `Assets<Tileset>` resource so we could use `Tilesets`, but we'll
@@ -49,7 +49,7 @@ fn show_tileset(
 	if let Some(_) = tilesets.get(handle) {
 		println!("Got tileset by handle! ({:?})", my_tileset.handle);
 	}
-	if let Some(tileset) = tilesets.get_by_id(&0) {
+	if let Some(tileset) = tilesets.get_by_id(&TilesetId(0)) {
 		println!("Got tileset by ID! ({})", tileset.id());
 	}
 	if let Some(tileset) = tilesets.get_by_name("My Awesome Tileset") {
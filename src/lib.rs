@@ -57,11 +57,31 @@
 //! }
 //! ```
 //!
+//! ## Hot Reloading
+//!
+//! Tilesets are reloaded whenever their configuration file, any of their tile definition files,
+//! or any of their tile textures change on disk, as long as Bevy's `file_watcher` feature is
+//! enabled (and `AssetPlugin::watch_for_changes_override` is not set to `false`). The entire
+//! tileset, including its atlas, is rebuilt and re-registered under its (possibly new) name and ID.
+//!
+//! Each reload increments the tileset's generation, which can be checked with
+//! `Tilesets::generation` to know when any data derived from the tileset should be refreshed.
+//!
 //! ## Crate Features
 //!
-//! * __`default`__ - No features automatically enabled
+//! * __`default`__ - Enables `fs_loader` and `multi-threaded`
+//! * __`fs_loader`__ - Enables loading tilesets synchronously from the filesystem, without running a Bevy app
+//! * __`multi-threaded`__ - Enables parsing tile definition files in parallel (also enables Bevy's `multi-threaded` feature)
 //! * __`variants`__ - Enables usage of Variant tiles
 //! * __`auto-tile`__ - Enables usage of Auto tiles
+//! * __`json`__ - Enables tile definition files written in JSON (`.json`)
+//! * __`yaml`__ - Enables tile definition files written in YAML (`.yaml`/`.yml`)
+//! * __`gzip`__ - Enables gzip-compressed tileset and tile definition files (e.g. `.ron.gz`)
+//! * __`zip`__ - Enables loading tilesets bundled in zip archives (`.tileset.zip`)
+//! * __`bevy_ecs_tilemap`__ - Enables helpers for using tilesets with `bevy_ecs_tilemap`
+//! * __`tiled`__ - Enables loading [Tiled](https://www.mapeditor.org/) `.tsx` tilesets as `Tileset` assets
+//! * __`ldtk`__ - Enables loading the tilesets of [LDtk](https://ldtk.io/) projects (`.ldtk`) as `Tileset` assets
+//! * __`aseprite`__ - Enables loading [Aseprite](https://www.aseprite.org/) files (`.ase`/`.aseprite`) as `Tileset` assets containing animated tiles
 //!
 
 /// A re-export of `bevy_tileset_core` in case non-prelude modules are needed